@@ -1,18 +1,299 @@
 use crate::models::{
-    GraphQLResponse, Problem, ProblemDetail, ProblemFilters, 
+    GraphQLRequest, GraphQLResponse, Problem, ProblemDetail, ProblemPage, ProblemFilters, 
+    ProblemIndex, ProblemIndexEntry, Site, ProblemSetQuestionList,
     ProblemSetQuestionListData, QuestionData, Difficulty,
     TestExecutionData, TestSubmissionResult, TestResult, TestStatus,
     SubmissionData, SubmissionCheckData, SubmissionDetails,
-    SubmissionResult, SubmissionStatus
+    SubmissionResult, SubmissionStatus, CheckResponse, InterpretResponse,
+    SubmitResponse, CaseOutcome, DailyChallenge, DailyChallengeData,
+    RandomQuestionData, GlobalData, UserStatus, DEFAULT_PAGE_SIZE
 };
-use anyhow::{Context, Result};
-use std::process::Command;
+use crate::rate_limit::TokenBucket;
+use crate::transport::{CurlTransport, HttpMethod, HttpRequest, HttpResponse, HttpTransport};
+use crate::error::{LeetCodeError, Result};
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use std::time::{Duration, Instant};
+
+/// Page size used when walking the full catalog to build the problem index
+const INDEX_PAGE_SIZE: i32 = 1000;
+
+const USER_AGENT: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36";
+
+const USER_STATUS_QUERY: &str = "query globalData {
+    userStatus {
+        isSignedIn
+        username
+        isPremium
+        avatar
+        realName
+    }
+}";
+
+const PROBLEM_LIST_QUERY: &str = "query problemsetQuestionList($categorySlug: String, $limit: Int, $skip: Int, $filters: QuestionListFilterInput) {
+    problemsetQuestionList(
+        categorySlug: $categorySlug
+        limit: $limit
+        skip: $skip
+        filters: $filters
+    ) {
+        total: totalNum
+        questions: data {
+            questionId
+            questionFrontendId
+            title
+            titleSlug
+            difficulty
+            topicTags {
+                name
+                slug
+            }
+            isPaidOnly
+            acRate
+            status
+        }
+    }
+}";
+
+const PROBLEM_DETAIL_QUERY: &str = "query questionData($titleSlug: String!) {
+    question(titleSlug: $titleSlug) {
+        questionId
+        questionFrontendId
+        title
+        titleSlug
+        content
+        difficulty
+        topicTags {
+            name
+            slug
+        }
+        codeSnippets {
+            lang
+            langSlug
+            code
+        }
+        sampleTestCase
+        exampleTestcases
+    }
+}";
+
+/// leetcode.cn variant of `PROBLEM_LIST_QUERY` that also selects the Chinese title
+const PROBLEM_LIST_QUERY_CN: &str = "query problemsetQuestionList($categorySlug: String, $limit: Int, $skip: Int, $filters: QuestionListFilterInput) {
+    problemsetQuestionList(
+        categorySlug: $categorySlug
+        limit: $limit
+        skip: $skip
+        filters: $filters
+    ) {
+        total
+        questions {
+            questionId
+            questionFrontendId: frontendQuestionId
+            title
+            translatedTitle: titleCn
+            titleSlug
+            difficulty
+            topicTags {
+                name
+                slug
+            }
+            isPaidOnly: paidOnly
+            acRate
+            status
+        }
+    }
+}";
+
+/// leetcode.cn variant of `PROBLEM_DETAIL_QUERY` that also selects translated title and content
+const PROBLEM_DETAIL_QUERY_CN: &str = "query questionData($titleSlug: String!) {
+    question(titleSlug: $titleSlug) {
+        questionId
+        questionFrontendId
+        title
+        translatedTitle
+        titleSlug
+        content
+        translatedContent
+        difficulty
+        topicTags {
+            name
+            slug
+        }
+        codeSnippets {
+            lang
+            langSlug
+            code
+        }
+        sampleTestCase
+        exampleTestcases
+    }
+}";
+
+const DAILY_CHALLENGE_QUERY: &str = "query questionOfToday {
+    activeDailyCodingChallengeQuestion {
+        date
+        userStatus
+        link
+        question {
+            titleSlug
+        }
+    }
+}";
+
+/// leetcode.cn exposes the daily challenge as `todayRecord`
+const DAILY_CHALLENGE_QUERY_CN: &str = "query questionOfToday {
+    todayRecord {
+        date
+        userStatus
+        question {
+            titleSlug
+        }
+    }
+}";
 
-const LEETCODE_GRAPHQL_URL: &str = "https://leetcode.com/graphql/";
+const RANDOM_QUESTION_QUERY: &str = "query randomQuestion($categorySlug: String, $filters: QuestionListFilterInput) {
+    randomQuestion(categorySlug: $categorySlug, filters: $filters) {
+        titleSlug
+    }
+}";
+
+/// leetcode.cn variant of `RANDOM_QUESTION_QUERY`, which returns the slug directly
+const RANDOM_QUESTION_QUERY_CN: &str = "query problemsetRandomFilteredQuestion($categorySlug: String!, $filters: QuestionListFilterInput) {
+    problemsetRandomFilteredQuestion(categorySlug: $categorySlug, filters: $filters)
+}";
+
+const INTERPRET_SOLUTION_MUTATION: &str = "mutation interpretSolution($titleSlug: String!, $code: String!, $lang: String!, $dataInput: String!) {
+    interpretSolution(titleSlug: $titleSlug, code: $code, lang: $lang, dataInput: $dataInput) {
+        submissionId
+    }
+}";
+
+const CHECK_INTERPRET_QUERY: &str = "query checkInterpret($submissionId: String!) {
+    interpretSolution(submissionId: $submissionId) {
+        submissionId
+        state
+        statusCode
+        status
+        runtime
+        memory
+        correctAnswer
+        totalTestcases
+        correctTestcases
+        compileError
+        runtimeError
+        lastTestcase
+        expectedOutput
+        codeOutput
+        stdOutput
+    }
+}";
 
-/// LeetCode API client using curl for HTTP requests
+const SUBMIT_SOLUTION_MUTATION: &str = "mutation submitSolution($titleSlug: String!, $code: String!, $lang: String!) {
+    submitSolution(titleSlug: $titleSlug, code: $code, lang: $lang) {
+        submissionId
+    }
+}";
+
+const CHECK_SUBMISSION_QUERY: &str = "query checkSubmission($submissionId: String!) {
+    submissionDetails(submissionId: $submissionId) {
+        submissionId
+        state
+        statusCode
+        status
+        runtime
+        memory
+        runtimePercentile
+        memoryPercentile
+        totalCorrect
+        totalTestcases
+        compileError
+        runtimeError
+        lastTestcase
+        expectedOutput
+        codeOutput
+    }
+}";
+
+/// Backoff and deadline settings for polling judge results
+///
+/// Slash commands run to completion on a single thread with nothing that could
+/// signal a cancellation, so the deadline is the only way polling ends early.
+#[derive(Debug, Clone)]
+pub struct PollOptions {
+    /// Delay before the second poll; doubled after every pending response
+    pub initial_interval: Duration,
+    /// Upper bound for the delay between polls
+    pub max_interval: Duration,
+    /// Overall deadline after which polling gives up
+    pub timeout: Duration,
+}
+
+impl PollOptions {
+    /// Default backoff with a custom overall deadline
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for PollOptions {
+    fn default() -> Self {
+        Self {
+            initial_interval: Duration::from_millis(500),
+            max_interval: Duration::from_secs(4),
+            timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Retry settings for idempotent requests that hit rate limits or server errors
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt; 0 disables retrying
+    pub max_retries: u32,
+    /// Backoff before the first retry; doubled for each further retry
+    pub initial_backoff: Duration,
+    /// Longest delay to wait, including a server-requested `Retry-After`
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Never retry
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Default::default()
+        }
+    }
+
+    /// Backoff with jitter before retry number `attempt` (starting at 0)
+    fn backoff(&self, attempt: u32) -> Duration {
+        let backoff = self.initial_backoff.saturating_mul(2u32.saturating_pow(attempt));
+        with_jitter(backoff).min(self.max_delay)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+/// LeetCode API client; uses curl for HTTP requests unless another transport is supplied
 pub struct LeetCodeApi {
     session_cookie: Option<String>,
+    csrf_token: Option<String>,
+    site: Site,
+    transport: Box<dyn HttpTransport>,
+    poll_options: PollOptions,
+    retry_policy: RetryPolicy,
+    rate_limiter: TokenBucket,
 }
 
 impl LeetCodeApi {
@@ -20,6 +301,12 @@ impl LeetCodeApi {
     pub fn new() -> Self {
         Self {
             session_cookie: None,
+            csrf_token: None,
+            site: Site::default(),
+            transport: Box::new(CurlTransport),
+            poll_options: PollOptions::default(),
+            retry_policy: RetryPolicy::default(),
+            rate_limiter: TokenBucket::default(),
         }
     }
 
@@ -27,26 +314,108 @@ impl LeetCodeApi {
     pub fn with_session(session_cookie: String) -> Self {
         Self {
             session_cookie: Some(session_cookie),
+            csrf_token: None,
+            site: Site::default(),
+            transport: Box::new(CurlTransport),
+            poll_options: PollOptions::default(),
+            retry_policy: RetryPolicy::default(),
+            rate_limiter: TokenBucket::default(),
         }
     }
 
-    /// Fetch problems list with optional filters
-    pub fn fetch_problems(&self, filters: &ProblemFilters) -> Result<Vec<Problem>> {
-        let query = self.build_problem_list_query(filters);
-        let response = self.execute_graphql_query(&query)?;
-        
-        let graphql_response: GraphQLResponse<ProblemSetQuestionListData> = 
-            serde_json::from_str(&response)
-                .context("Failed to parse GraphQL response")?;
+    /// Set the CSRF token required by code execution mutations
+    pub fn with_csrf_token(mut self, csrf_token: String) -> Self {
+        self.csrf_token = Some(csrf_token);
+        self
+    }
+
+    /// Target a different LeetCode site (leetcode.com or leetcode.cn)
+    pub fn with_site(mut self, site: Site) -> Self {
+        self.site = site;
+        self
+    }
+
+    /// Site this client talks to
+    pub fn site(&self) -> Site {
+        self.site
+    }
+
+    /// Configure how judge results are polled after test runs and submissions
+    pub fn with_poll_options(mut self, poll_options: PollOptions) -> Self {
+        self.poll_options = poll_options;
+        self
+    }
+
+    /// Configure retrying of idempotent requests
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Replace the client-side rate limiter
+    pub fn with_rate_limiter(mut self, rate_limiter: TokenBucket) -> Self {
+        self.rate_limiter = rate_limiter;
+        self
+    }
+
+    /// Replace the HTTP transport (e.g. with `MockTransport` in tests)
+    pub fn with_transport<T: HttpTransport + 'static>(mut self, transport: T) -> Self {
+        self.transport = Box::new(transport);
+        self
+    }
+
+    /// Fetch one page of the problem list with optional filters
+    pub fn fetch_problems(&self, filters: &ProblemFilters) -> Result<ProblemPage> {
+        let list = self.fetch_problem_page(filters)?;
+
+        Ok(ProblemPage {
+            total: list.total,
+            skip: filters.skip.unwrap_or(0),
+            limit: filters.limit.unwrap_or(DEFAULT_PAGE_SIZE),
+            questions: list.questions,
+        })
+    }
 
-        if let Some(errors) = graphql_response.errors {
-            return Err(anyhow::anyhow!("GraphQL errors: {:?}", errors));
+    /// Build the frontend ID / title → slug index by paging through the full catalog
+    pub fn fetch_problem_index(&self) -> Result<ProblemIndex> {
+        let problems = self.fetch_problem_catalog()?;
+
+        Ok(ProblemIndex::new(problems.iter().map(ProblemIndexEntry::from).collect()))
+    }
+
+    /// Page through the entire `problemsetQuestionList`
+    ///
+    /// Each page goes through the client-side rate limiter like any other request.
+    pub fn fetch_problem_catalog(&self) -> Result<Vec<Problem>> {
+        let mut problems = Vec::new();
+        let mut filters = ProblemFilters {
+            limit: Some(INDEX_PAGE_SIZE),
+            ..Default::default()
+        };
+
+        loop {
+            filters.skip = Some(problems.len() as i32);
+            let page = self.fetch_problem_page(&filters)?;
+            let fetched = page.questions.len();
+
+            problems.extend(page.questions);
+
+            if fetched == 0 || problems.len() as i32 >= page.total {
+                break;
+            }
         }
 
-        let data = graphql_response.data
-            .context("No data in GraphQL response")?;
+        Ok(problems)
+    }
+
+    /// Fetch a single page of the problem list including the server total
+    fn fetch_problem_page(&self, filters: &ProblemFilters) -> Result<ProblemSetQuestionList> {
+        let query = self.build_problem_list_query(filters);
+        let response = self.execute_graphql_query(&query)?;
+        
+        let data: ProblemSetQuestionListData = parse_graphql(&response, "GraphQL response")?;
 
-        Ok(data.problemset_question_list.questions)
+        Ok(data.problemset_question_list)
     }
 
     /// Fetch problem details by title slug
@@ -54,68 +423,91 @@ impl LeetCodeApi {
         let query = self.build_problem_detail_query(title_slug);
         let response = self.execute_graphql_query(&query)?;
 
-        let graphql_response: GraphQLResponse<QuestionData> = 
-            serde_json::from_str(&response)
-                .context("Failed to parse GraphQL response")?;
+        // Premium-only questions come back with `content: null` for non-subscribers
+        let data: QuestionData = match parse_graphql(&response, "GraphQL response") {
+            Err(LeetCodeError::Parse(_)) if is_locked_question(&response) => {
+                return Err(LeetCodeError::PremiumRequired);
+            },
+            result => result?,
+        };
 
-        if let Some(errors) = graphql_response.errors {
-            return Err(anyhow::anyhow!("GraphQL errors: {:?}", errors));
-        }
+        Ok(data.question)
+    }
+
+    /// Fetch today's coding challenge
+    ///
+    /// Only the slug is selected here; use `fetch_problem_detail` for the full question.
+    pub fn fetch_daily_challenge(&self) -> Result<DailyChallenge> {
+        let document = match self.site {
+            Site::Global => DAILY_CHALLENGE_QUERY,
+            Site::China => DAILY_CHALLENGE_QUERY_CN,
+        };
+        let query = GraphQLRequest::new("questionOfToday", document, json!({}));
+        let response = self.execute_graphql_query(&query)?;
 
-        let data = graphql_response.data
-            .context("No data in GraphQL response")?;
+        let data: DailyChallengeData = parse_graphql(&response, "daily challenge response")?;
+        data.into_challenge()
+            .ok_or_else(|| LeetCodeError::NotFound("today's daily challenge".to_string()))
+    }
 
-        Ok(data.question)
+    /// Pick a random question matching `filters`
+    ///
+    /// Returns the title slug, or None when no question matches.
+    pub fn fetch_random_question(&self, filters: &ProblemFilters) -> Result<Option<String>> {
+        let (operation_name, document) = match self.site {
+            Site::Global => ("randomQuestion", RANDOM_QUESTION_QUERY),
+            Site::China => ("problemsetRandomFilteredQuestion", RANDOM_QUESTION_QUERY_CN),
+        };
+        let query = GraphQLRequest::new(
+            operation_name,
+            document,
+            json!({
+                "categorySlug": "all-code-problems",
+                "filters": self.build_filter_object(filters),
+            }),
+        );
+        let response = self.execute_graphql_query(&query)?;
+
+        let data: RandomQuestionData = parse_graphql(&response, "random question response")?;
+        Ok(data.into_title_slug())
     }
 
     /// Verify authentication by checking user profile
+    ///
+    /// Only a rejected session or `isSignedIn: false` counts as signed out;
+    /// rate limiting and server errors are returned as errors.
     pub fn verify_authentication(&self, session_cookie: &str) -> Result<bool> {
-        let query = r#"{
-            "query": "query globalData { 
-                userStatus { 
-                    isSignedIn 
-                    username 
-                } 
-            }"
-        }"#;
-
-        let mut cmd = Command::new("curl");
-        cmd.args(["-s", "-X", "POST", LEETCODE_GRAPHQL_URL])
-            .args(["-H", "Content-Type: application/json"])
-            .args(["-H", "User-Agent: Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36"])
-            .args(["-H", &format!("Cookie: {}", session_cookie)])
-            .args(["-d", query]);
-
-        let output = cmd.output()
-            .context("Failed to execute authentication curl command")?;
-
-        if !output.status.success() {
-            return Ok(false);
-        }
-
-        let response = String::from_utf8(output.stdout)
-            .context("Failed to parse authentication response as UTF-8")?;
-
-        // Parse response to check if user is signed in
-        if let Ok(json) = serde_json::from_str::<serde_json::Value>(&response) {
-            if let Some(user_status) = json.get("data").and_then(|d| d.get("userStatus")) {
-                if let Some(is_signed_in) = user_status.get("isSignedIn") {
-                    return Ok(is_signed_in.as_bool().unwrap_or(false));
-                }
-            }
+        match self.fetch_user_status(session_cookie) {
+            Ok(user_status) => Ok(user_status.is_signed_in),
+            Err(LeetCodeError::Unauthenticated) => Ok(false),
+            Err(e) => Err(e),
         }
+    }
+
+    /// Signed-in state and username for `session_cookie`
+    pub fn fetch_user_status(&self, session_cookie: &str) -> Result<UserStatus> {
+        let query = GraphQLRequest::new("globalData", USER_STATUS_QUERY, json!({}));
+        let body = to_json(&query)?;
+
+        let request = self.build_graphql_request(body, Some(session_cookie));
+        let response = self.dispatch(&request)?;
 
-        Ok(false)
+        if !response.is_success() {
+            return Err(Self::status_error(&response, &request));
+        }
+
+        let data: GlobalData = parse_graphql(&response.body, "user status response")?;
+        Ok(data.user_status)
     }
 
-    /// Execute test for a problem solution
-    pub fn run_test(&self, title_slug: &str, code: &str, lang: &str) -> Result<TestResult> {
+    /// Execute test for a problem solution against `data_input` (newline-separated arguments)
+    pub fn run_test(&self, title_slug: &str, code: &str, lang: &str, data_input: &str) -> Result<TestResult> {
         if self.session_cookie.is_none() {
-            return Err(anyhow::anyhow!("Authentication required for testing"));
+            return Err(LeetCodeError::Unauthenticated);
         }
 
         // Step 1: Submit code for testing
-        let submission_id = self.submit_for_test(title_slug, code, lang)?;
+        let submission_id = self.submit_for_test(title_slug, code, lang, data_input)?;
 
         // Step 2: Poll for results
         let result = self.poll_test_result(&submission_id)?;
@@ -124,113 +516,40 @@ impl LeetCodeApi {
     }
 
     /// Submit code for testing and return submission ID
-    fn submit_for_test(&self, title_slug: &str, code: &str, lang: &str) -> Result<String> {
-        let query = format!(
-            r#"{{
-                "query": "mutation interpretSolution($titleSlug: String!, $code: String!, $lang: String!) {{
-                    interpretSolution(titleSlug: $titleSlug, code: $code, lang: $lang) {{
-                        submissionId
-                    }}
-                }}",
-                "variables": {{
-                    "titleSlug": "{}",
-                    "code": "{}",
-                    "lang": "{}"
-                }}
-            }}"#,
-            title_slug,
-            code.replace('"', "\\\"").replace('\n', "\\n"),
-            lang
-        );
+    fn submit_for_test(&self, title_slug: &str, code: &str, lang: &str, data_input: &str) -> Result<String> {
+        let mut query = Self::build_code_mutation("interpretSolution", INTERPRET_SOLUTION_MUTATION, title_slug, code, lang);
+        query.variables["dataInput"] = json!(data_input);
 
-        let response = self.execute_graphql_query(&query)?;
-        let graphql_response: GraphQLResponse<TestExecutionData> = 
-            serde_json::from_str(&response)
-                .context("Failed to parse test submission response")?;
-
-        if let Some(errors) = graphql_response.errors {
-            return Err(anyhow::anyhow!("GraphQL errors: {:?}", errors));
-        }
-
-        let data = graphql_response.data
-            .context("No data in test submission response")?;
+        let response = self.execute_graphql_mutation(&query, title_slug)?;
+        let data: TestExecutionData = parse_graphql(&response, "test submission response")?;
 
         let interpret_solution = data.interpret_solution
-            .context("No interpretation result in response")?;
+            .ok_or_else(|| LeetCodeError::Parse("No interpretation result in response".to_string()))?;
 
         Ok(interpret_solution.submission_id)
     }
 
     /// Poll for test result by submission ID
     fn poll_test_result(&self, submission_id: &str) -> Result<TestResult> {
-        let max_polls = 30; // Poll for up to 30 seconds
-        let poll_interval = std::time::Duration::from_secs(1);
-
-        for _ in 0..max_polls {
-            let query = format!(
-                r#"{{
-                    "query": "query checkInterpret($submissionId: String!) {{
-                        interpretSolution(submissionId: $submissionId) {{
-                            submissionId
-                            statusCode
-                            status
-                            runtime
-                            memory
-                            correctAnswer
-                            totalTestcases
-                            correctTestcases
-                            compileError
-                            runtimeError
-                            lastTestcase
-                            expectedOutput
-                            codeOutput
-                        }}
-                    }}",
-                    "variables": {{
-                        "submissionId": "{}"
-                    }}
-                }}"#,
-                submission_id
+        self.poll_judge(submission_id, || {
+            let query = GraphQLRequest::new(
+                "checkInterpret",
+                CHECK_INTERPRET_QUERY,
+                json!({ "submissionId": submission_id }),
             );
 
             let response = self.execute_graphql_query(&query)?;
-            let graphql_response: GraphQLResponse<TestExecutionData> = 
-                serde_json::from_str(&response)
-                    .context("Failed to parse test result response")?;
-
-            if let Some(errors) = graphql_response.errors {
-                return Err(anyhow::anyhow!("GraphQL errors: {:?}", errors));
-            }
-
-            if let Some(data) = graphql_response.data {
-                if let Some(interpret_result) = data.interpret_solution {
-                    // Check if execution is complete
-                    if interpret_result.status_code == 10 { // SUCCESS
-                        return Ok(self.parse_test_result(interpret_result));
-                    } else if interpret_result.status_code > 10 { // ERROR or COMPLETE
-                        return Ok(self.parse_test_result(interpret_result));
-                    }
-                    // If status_code is < 10, continue polling (still running)
-                }
-            }
-
-            std::thread::sleep(poll_interval);
-        }
+            let data: TestExecutionData = parse_graphql(&response, "test result response")?;
 
-        Err(anyhow::anyhow!("Test execution timed out"))
+            Ok(data.interpret_solution
+                .filter(|result| !is_judge_pending(result.state.as_deref()))
+                .map(|result| self.parse_test_result(result)))
+        })
     }
 
     /// Parse API result into TestResult structure
     fn parse_test_result(&self, result: TestSubmissionResult) -> TestResult {
-        let status = match result.status_code {
-            10 => TestStatus::Success,
-            11 => TestStatus::WrongAnswer,
-            12 => TestStatus::MemoryLimitExceeded,
-            13 => TestStatus::TimeLimitExceeded,
-            14 => TestStatus::RuntimeError,
-            15 => TestStatus::CompileError,
-            _ => TestStatus::UnknownError,
-        };
+        let status = TestStatus::from_status_code(result.status_code);
 
         let runtime = result.runtime
             .and_then(|r| r.parse::<u32>().ok());
@@ -268,13 +587,15 @@ impl LeetCodeApi {
             total_tests: result.total_testcases.unwrap_or(0),
             failed_test_case,
             compile_error: result.compile_error.or(result.runtime_error),
+            cases: Vec::new(),
+            stdout: result.std_output.filter(|out| !out.is_empty()),
         }
     }
 
     /// Submit solution to LeetCode
     pub fn submit_solution(&self, title_slug: &str, code: &str, lang: &str) -> Result<SubmissionResult> {
         if self.session_cookie.is_none() {
-            return Err(anyhow::anyhow!("Authentication required for solution submission"));
+            return Err(LeetCodeError::Unauthenticated);
         }
 
         // Step 1: Submit solution
@@ -288,113 +609,38 @@ impl LeetCodeApi {
 
     /// Submit code and return submission ID
     fn submit_code(&self, title_slug: &str, code: &str, lang: &str) -> Result<String> {
-        let query = format!(
-            r#"{{
-                "query": "mutation submitSolution($titleSlug: String!, $code: String!, $lang: String!) {{
-                    submitSolution(titleSlug: $titleSlug, code: $code, lang: $lang) {{
-                        submissionId
-                    }}
-                }}",
-                "variables": {{
-                    "titleSlug": "{}",
-                    "code": "{}",
-                    "lang": "{}"
-                }}
-            }}"#,
-            title_slug,
-            code.replace('"', "\\\"").replace('\n', "\\n"),
-            lang
-        );
+        let query = Self::build_code_mutation("submitSolution", SUBMIT_SOLUTION_MUTATION, title_slug, code, lang);
 
-        let response = self.execute_graphql_query(&query)?;
-        let graphql_response: GraphQLResponse<SubmissionData> = 
-            serde_json::from_str(&response)
-                .context("Failed to parse submission response")?;
-
-        if let Some(errors) = graphql_response.errors {
-            return Err(anyhow::anyhow!("GraphQL errors: {:?}", errors));
-        }
-
-        let data = graphql_response.data
-            .context("No data in submission response")?;
+        let response = self.execute_graphql_mutation(&query, title_slug)?;
+        let data: SubmissionData = parse_graphql(&response, "submission response")?;
 
         let submit_info = data.submit_solution
-            .context("No submission result in response")?;
+            .ok_or_else(|| LeetCodeError::Parse("No submission result in response".to_string()))?;
 
         Ok(submit_info.submission_id)
     }
 
     /// Poll for submission result by submission ID
     fn poll_submission_result(&self, submission_id: &str) -> Result<SubmissionResult> {
-        let max_polls = 30; // Poll for up to 30 seconds
-        let poll_interval = std::time::Duration::from_secs(1);
-
-        for _ in 0..max_polls {
-            let query = format!(
-                r#"{{
-                    "query": "query checkSubmission($submissionId: String!) {{
-                        submissionDetails(submissionId: $submissionId) {{
-                            submissionId
-                            statusCode
-                            status
-                            runtime
-                            memory
-                            runtimePercentile
-                            memoryPercentile
-                            totalCorrect
-                            totalTestcases
-                            compileError
-                            runtimeError
-                            lastTestcase
-                            expectedOutput
-                            codeOutput
-                        }}
-                    }}",
-                    "variables": {{
-                        "submissionId": "{}"
-                    }}
-                }}"#,
-                submission_id
+        self.poll_judge(submission_id, || {
+            let query = GraphQLRequest::new(
+                "checkSubmission",
+                CHECK_SUBMISSION_QUERY,
+                json!({ "submissionId": submission_id }),
             );
 
             let response = self.execute_graphql_query(&query)?;
-            let graphql_response: GraphQLResponse<SubmissionCheckData> = 
-                serde_json::from_str(&response)
-                    .context("Failed to parse submission result response")?;
-
-            if let Some(errors) = graphql_response.errors {
-                return Err(anyhow::anyhow!("GraphQL errors: {:?}", errors));
-            }
+            let data: SubmissionCheckData = parse_graphql(&response, "submission result response")?;
 
-            if let Some(data) = graphql_response.data {
-                if let Some(details) = data.submission_details {
-                    // Check if submission is complete
-                    if details.status_code >= 10 { // Complete (success or error)
-                        return Ok(self.parse_submission_result(details));
-                    }
-                    // If status_code < 10, continue polling (still running)
-                }
-            }
-
-            std::thread::sleep(poll_interval);
-        }
-
-        Err(anyhow::anyhow!("Submission polling timed out"))
+            Ok(data.submission_details
+                .filter(|details| !is_judge_pending(details.state.as_deref()))
+                .map(|details| self.parse_submission_result(details)))
+        })
     }
 
     /// Parse API result into SubmissionResult structure
     fn parse_submission_result(&self, details: SubmissionDetails) -> SubmissionResult {
-        let status = match details.status_code {
-            10 => SubmissionStatus::Accepted,
-            11 => SubmissionStatus::WrongAnswer,
-            12 => SubmissionStatus::MemoryLimitExceeded,
-            13 => SubmissionStatus::TimeLimitExceeded,
-            14 => SubmissionStatus::RuntimeError,
-            15 => SubmissionStatus::CompileError,
-            16 => SubmissionStatus::OutputLimitExceeded,
-            20 => SubmissionStatus::InternalError,
-            _ => SubmissionStatus::Unknown,
-        };
+        let status = SubmissionStatus::from_status_code(details.status_code);
 
         let runtime = details.runtime
             .and_then(|r| r.parse::<u32>().ok());
@@ -438,118 +684,398 @@ impl LeetCodeApi {
         }
     }
 
-    /// Execute GraphQL query using curl
-    fn execute_graphql_query(&self, query: &str) -> Result<String> {
-        let mut cmd = Command::new("curl");
-        cmd.args(["-s", "-X", "POST", LEETCODE_GRAPHQL_URL])
-            .args(["-H", "Content-Type: application/json"])
-            .args(["-H", "User-Agent: Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36"]);
+    /// Run code against custom input via `POST /problems/{slug}/interpret_solution/`
+    pub fn run_test_rest(&self, problem: &ProblemDetail, code: &str, lang: &str, data_input: &str) -> Result<TestResult> {
+        if self.session_cookie.is_none() {
+            return Err(LeetCodeError::Unauthenticated);
+        }
+
+        let url = format!("{}/problems/{}/interpret_solution/", self.site.base_url(), problem.title_slug);
+        let body = json!({
+            "lang": lang,
+            "question_id": problem.id,
+            "typed_code": code,
+            "data_input": data_input,
+        });
+
+        let response = self.post_rest(&url, &body, &problem.title_slug)?;
+        let interpret: InterpretResponse = parse_json(&response, "interpret response")?;
+
+        let check = self.poll_check(&interpret.interpret_id, &problem.title_slug)?;
+        Ok(Self::parse_check_test_result(check, data_input))
+    }
 
-        // Add session cookie if available
-        if let Some(ref cookie) = self.session_cookie {
-            cmd.args(["-H", &format!("Cookie: {}", cookie)]);
+    /// Submit code via `POST /problems/{slug}/submit/`
+    pub fn submit_solution_rest(&self, problem: &ProblemDetail, code: &str, lang: &str) -> Result<SubmissionResult> {
+        if self.session_cookie.is_none() {
+            return Err(LeetCodeError::Unauthenticated);
         }
 
-        cmd.args(["-d", query]);
+        let url = format!("{}/problems/{}/submit/", self.site.base_url(), problem.title_slug);
+        let body = json!({
+            "lang": lang,
+            "question_id": problem.id,
+            "typed_code": code,
+        });
+
+        let response = self.post_rest(&url, &body, &problem.title_slug)?;
+        let submit: SubmitResponse = parse_json(&response, "submit response")?;
+
+        let check = self.poll_check(&submit.submission_id.to_string(), &problem.title_slug)?;
+        Ok(Self::parse_check_submission_result(check))
+    }
+
+    /// POST a JSON body to a REST endpoint with CSRF headers
+    fn post_rest(&self, url: &str, body: &serde_json::Value, title_slug: &str) -> Result<String> {
+        let request = HttpRequest::post(url, body.to_string())
+            .header("Content-Type", "application/json")
+            .header("User-Agent", USER_AGENT);
 
-        let output = cmd.output()
-            .context("Failed to execute curl command")?;
+        self.send_request(&self.authorize_mutation(request, title_slug)?)
+    }
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(anyhow::anyhow!("curl command failed: {}", stderr));
+    /// Poll `GET /submissions/detail/{id}/check/` until the judge finishes
+    fn poll_check(&self, id: &str, title_slug: &str) -> Result<CheckResponse> {
+        let url = format!("{}/submissions/detail/{}/check/", self.site.base_url(), id);
+
+        self.poll_judge(id, || {
+            let request = HttpRequest::get(&url)
+                .header("User-Agent", USER_AGENT);
+            let response = self.send_request(&self.authorize_mutation(request, title_slug)?)?;
+
+            let check: CheckResponse = parse_json(&response, "check response")?;
+
+            Ok(Some(check).filter(CheckResponse::is_complete))
+        })
+    }
+
+    /// Call `poll` until it yields a result, backing off exponentially with jitter
+    ///
+    /// Gives up at the `PollOptions` deadline with an error carrying the submission
+    /// ID so the result can be looked up later.
+    fn poll_judge<T>(&self, submission_id: &str, mut poll: impl FnMut() -> Result<Option<T>>) -> Result<T> {
+        let options = &self.poll_options;
+        let deadline = Instant::now() + options.timeout;
+        let mut interval = options.initial_interval;
+
+        loop {
+            if let Some(result) = poll()? {
+                return Ok(result);
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(LeetCodeError::Judge(format!(
+                    "Judge did not finish within {}s for submission {}. Check {}/submissions/detail/{}/ later",
+                    options.timeout.as_secs(), submission_id, self.site.base_url(), submission_id
+                )));
+            }
+
+            std::thread::sleep(with_jitter(interval).min(remaining));
+            interval = (interval * 2).min(options.max_interval);
+        }
+    }
+
+    /// Map a check-endpoint response for a test run into TestResult
+    ///
+    /// A finished run (status 10) whose answers differ is a wrong answer.
+    fn parse_check_test_result(check: CheckResponse, data_input: &str) -> TestResult {
+        let status = match check.status_code {
+            Some(10) if check.correct_answer == Some(false) => TestStatus::WrongAnswer,
+            status_code => status_code.map_or(TestStatus::UnknownError, TestStatus::from_status_code),
+        };
+
+        let failed_test_case = if status != TestStatus::Success {
+            let mut failure_info = Vec::new();
+
+            if !data_input.is_empty() {
+                failure_info.push(format!("Input: {}", data_input));
+            }
+            if !check.expected_code_answer.is_empty() {
+                failure_info.push(format!("Expected: {}", check.expected_code_answer.join("\n")));
+            }
+            if !check.code_answer.is_empty() {
+                failure_info.push(format!("Actual: {}", check.code_answer.join("\n")));
+            }
+
+            if !failure_info.is_empty() {
+                Some(failure_info.join("\n"))
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+
+        let cases = Self::build_case_outcomes(&check, data_input);
+        let total_tests = check.total_testcases.unwrap_or(cases.len() as u32);
+        let passed_tests = check.total_correct
+            .unwrap_or_else(|| cases.iter().filter(|case| case.passed).count() as u32);
+
+        TestResult {
+            status,
+            runtime: check.status_runtime.as_deref().and_then(parse_metric).map(|r| r as u32),
+            memory: check.status_memory.as_deref().and_then(parse_metric),
+            passed_tests,
+            total_tests,
+            failed_test_case,
+            compile_error: check.full_compile_error.or(check.compile_error)
+                .or(check.full_runtime_error)
+                .or(check.runtime_error),
+            cases,
+            stdout: check.std_output.filter(|out| !out.is_empty()),
         }
+    }
 
-        let response = String::from_utf8(output.stdout)
-            .context("Failed to parse curl output as UTF-8")?;
+    /// Pair each testcase's input with its expected/actual answers and stdout
+    ///
+    /// Pass/fail comes from `compare_result` when present, otherwise from comparing answers.
+    fn build_case_outcomes(check: &CheckResponse, data_input: &str) -> Vec<CaseOutcome> {
+        let count = check.total_testcases
+            .map(|total| total as usize)
+            .unwrap_or(check.code_answer.len().max(check.expected_code_answer.len()));
+        if count == 0 {
+            return Vec::new();
+        }
 
-        Ok(response)
+        let inputs = split_testcases(data_input, count);
+        let compare: Vec<char> = check.compare_result.as_deref().unwrap_or("").chars().collect();
+
+        (0..count)
+            .map(|i| {
+                let expected = check.expected_code_answer.get(i).cloned().unwrap_or_default();
+                let actual = check.code_answer.get(i).cloned().unwrap_or_default();
+                let passed = match compare.get(i) {
+                    Some(flag) => *flag == '1',
+                    None => check.code_answer.get(i).is_some() && actual == expected,
+                };
+
+                CaseOutcome {
+                    input: inputs.get(i).cloned().unwrap_or_default(),
+                    expected,
+                    actual,
+                    stdout: check.std_output_list.get(i).filter(|out| !out.is_empty()).cloned(),
+                    passed,
+                }
+            })
+            .collect()
+    }
+
+    /// Map a check-endpoint response for a submission into SubmissionResult
+    fn parse_check_submission_result(check: CheckResponse) -> SubmissionResult {
+        let status = check.status_code.map_or(SubmissionStatus::Unknown, SubmissionStatus::from_status_code);
+
+        let failed_test_case = if status != SubmissionStatus::Accepted {
+            let mut failure_info = Vec::new();
+
+            if let Some(input) = check.last_testcase.filter(|s| !s.is_empty()) {
+                failure_info.push(format!("Input: {}", input));
+            }
+            if let Some(expected) = check.expected_output.filter(|s| !s.is_empty()) {
+                failure_info.push(format!("Expected: {}", expected));
+            }
+            if let Some(actual) = check.code_output.filter(|s| !s.is_empty()) {
+                failure_info.push(format!("Actual: {}", actual));
+            }
+            if let Some(stdout) = check.std_output.filter(|s| !s.is_empty()) {
+                failure_info.push(format!("Stdout: {}", stdout));
+            }
+
+            if !failure_info.is_empty() {
+                Some(failure_info.join("\n"))
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+
+        SubmissionResult {
+            status,
+            runtime: check.status_runtime.as_deref().and_then(parse_metric).map(|r| r as u32),
+            memory: check.status_memory.as_deref().and_then(parse_metric),
+            runtime_percentile: check.runtime_percentile,
+            memory_percentile: check.memory_percentile,
+            total_correct: check.total_correct,
+            total_testcases: check.total_testcases,
+            failed_test_case,
+            compile_error: check.full_compile_error.or(check.compile_error),
+            runtime_error: check.full_runtime_error.or(check.runtime_error),
+        }
+    }
+
+    /// Execute GraphQL query through the configured transport
+    fn execute_graphql_query(&self, query: &GraphQLRequest) -> Result<String> {
+        let body = to_json(query)?;
+        let request = self.build_graphql_request(body, self.session_cookie.as_deref());
+
+        self.send_request(&request)
+    }
+
+    /// Execute a GraphQL mutation with the CSRF headers LeetCode requires
+    fn execute_graphql_mutation(&self, query: &GraphQLRequest, title_slug: &str) -> Result<String> {
+        let body = to_json(query)?;
+        let request = self.authorize_mutation(self.build_graphql_request(body, None), title_slug)?;
+
+        self.send_request(&request)
+    }
+
+    /// Send a request and return the body of a successful response
+    ///
+    /// Idempotent requests (GETs and GraphQL queries, not mutations) are retried on
+    /// rate limits and network/server errors, waiting for `Retry-After` when given.
+    fn send_request(&self, request: &HttpRequest) -> Result<String> {
+        let idempotent = is_idempotent(request);
+        let mut attempt = 0;
+
+        loop {
+            let error = match self.dispatch(request) {
+                Ok(response) if response.is_success() => return Ok(response.body),
+                Ok(response) => Self::status_error(&response, request),
+                Err(error) => error,
+            };
+
+            if !idempotent || !error.is_retryable() || attempt >= self.retry_policy.max_retries {
+                return Err(error);
+            }
+
+            let delay = match error {
+                LeetCodeError::RateLimited { retry_after: Some(retry_after) } => retry_after,
+                _ => self.retry_policy.backoff(attempt),
+            };
+            if delay > self.retry_policy.max_delay {
+                return Err(error);
+            }
+
+            std::thread::sleep(delay);
+            attempt += 1;
+        }
+    }
+
+    /// Send a single request through the rate limiter and transport
+    fn dispatch(&self, request: &HttpRequest) -> Result<HttpResponse> {
+        self.rate_limiter.acquire();
+        self.transport.send(request)
+            .map_err(|e| LeetCodeError::Network(format!("{:#}", e)))
+    }
+
+    /// Classify an unsuccessful response, keeping any `Retry-After` delay
+    ///
+    /// Client errors LeetCode explains with GraphQL `errors` are classified
+    /// by those messages instead of the bare status.
+    fn status_error(response: &HttpResponse, request: &HttpRequest) -> LeetCodeError {
+        let csrf_sent = request.header_value("x-csrftoken").is_some();
+        match LeetCodeError::from_status(response.status, csrf_sent) {
+            LeetCodeError::RateLimited { .. } => LeetCodeError::RateLimited { retry_after: response.retry_after() },
+            LeetCodeError::Api(message) => serde_json::from_str::<GraphQLResponse<serde_json::Value>>(&response.body)
+                .ok()
+                .and_then(|response| response.errors)
+                .filter(|errors| !errors.is_empty())
+                .map(|errors| LeetCodeError::from_graphql_errors(&errors))
+                .unwrap_or(LeetCodeError::Api(message)),
+            error => error,
+        }
+    }
+
+    /// Build a GraphQL POST request with the standard headers
+    fn build_graphql_request(&self, body: String, session_cookie: Option<&str>) -> HttpRequest {
+        let request = HttpRequest::post(&self.site.graphql_url(), body)
+            .header("Content-Type", "application/json")
+            .header("User-Agent", USER_AGENT);
+
+        match session_cookie {
+            Some(cookie) => request.header("Cookie", &self.site.cookie_header(cookie)),
+            None => request,
+        }
+    }
+
+    /// CSRF token from `with_csrf_token`, or the `csrftoken` pair inside the session cookie
+    fn csrf_token(&self) -> Option<String> {
+        if let Some(ref token) = self.csrf_token {
+            return Some(token.clone());
+        }
+
+        let prefix = format!("{}=", self.site.csrf_cookie_name());
+        self.session_cookie.as_deref()?
+            .split(';')
+            .map(str::trim)
+            .find_map(|pair| pair.strip_prefix(prefix.as_str()))
+            .map(str::to_string)
+    }
+
+    /// Attach session cookie, `csrftoken` cookie, `x-csrftoken` and `Referer` headers
+    fn authorize_mutation(&self, request: HttpRequest, title_slug: &str) -> Result<HttpRequest> {
+        let session_cookie = self.session_cookie.as_deref()
+            .ok_or(LeetCodeError::Unauthenticated)?;
+        let csrf_token = self.csrf_token()
+            .ok_or(LeetCodeError::CsrfMismatch)?;
+
+        let csrf_cookie_name = self.site.csrf_cookie_name();
+        let mut cookie = self.site.cookie_header(session_cookie);
+        if !cookie.contains(&format!("{}=", csrf_cookie_name)) {
+            cookie.push_str(&format!("; {}={}", csrf_cookie_name, csrf_token));
+        }
+
+        Ok(request
+            .header("Cookie", &cookie)
+            .header("x-csrftoken", &csrf_token)
+            .header("Referer", &format!("{}/problems/{}/", self.site.base_url(), title_slug))
+            .header("Origin", self.site.base_url()))
     }
 
     /// Build GraphQL query for problem list
-    fn build_problem_list_query(&self, filters: &ProblemFilters) -> String {
+    fn build_problem_list_query(&self, filters: &ProblemFilters) -> GraphQLRequest {
         let category_slug = "all-code-problems";
         let skip = filters.skip.unwrap_or(0);
-        let limit = filters.limit.unwrap_or(50);
-
-        let filter_obj = self.build_filter_object(filters);
-
-        let query = format!(
-            r#"{{
-                "query": "query problemsetQuestionList($categorySlug: String, $limit: Int, $skip: Int, $filters: QuestionListFilterInput) {{
-                    problemsetQuestionList(
-                        categorySlug: $categorySlug
-                        limit: $limit
-                        skip: $skip
-                        filters: $filters
-                    ) {{
-                        total: totalNum
-                        questions: data {{
-                            questionId
-                            questionFrontendId
-                            title
-                            titleSlug
-                            difficulty
-                            topicTags {{
-                                name
-                                slug
-                            }}
-                            isPaidOnly
-                            acRate
-                        }}
-                    }}
-                }}",
-                "variables": {{
-                    "categorySlug": "{}",
-                    "skip": {},
-                    "limit": {},
-                    "filters": {}
-                }}
-            }}"#,
-            category_slug, skip, limit, filter_obj
-        );
+        let limit = filters.limit.unwrap_or(DEFAULT_PAGE_SIZE);
+
+        let document = match self.site {
+            Site::Global => PROBLEM_LIST_QUERY,
+            Site::China => PROBLEM_LIST_QUERY_CN,
+        };
 
-        query
+        GraphQLRequest::new(
+            "problemsetQuestionList",
+            document,
+            json!({
+                "categorySlug": category_slug,
+                "skip": skip,
+                "limit": limit,
+                "filters": self.build_filter_object(filters),
+            }),
+        )
     }
 
     /// Build GraphQL query for problem details
-    fn build_problem_detail_query(&self, title_slug: &str) -> String {
-        let query = format!(
-            r#"{{
-                "query": "query questionData($titleSlug: String!) {{
-                    question(titleSlug: $titleSlug) {{
-                        questionId
-                        questionFrontendId
-                        title
-                        titleSlug
-                        content
-                        difficulty
-                        topicTags {{
-                            name
-                            slug
-                        }}
-                        codeSnippets {{
-                            lang
-                            langSlug
-                            code
-                        }}
-                        sampleTestCase
-                        exampleTestcases
-                    }}
-                }}",
-                "variables": {{
-                    "titleSlug": "{}"
-                }}
-            }}"#,
-            title_slug
-        );
+    fn build_problem_detail_query(&self, title_slug: &str) -> GraphQLRequest {
+        let document = match self.site {
+            Site::Global => PROBLEM_DETAIL_QUERY,
+            Site::China => PROBLEM_DETAIL_QUERY_CN,
+        };
+
+        GraphQLRequest::new(
+            "questionData",
+            document,
+            json!({ "titleSlug": title_slug }),
+        )
+    }
 
-        query
+    /// Build interpret/submit mutation carrying the solution source
+    fn build_code_mutation(operation_name: &str, mutation: &str, title_slug: &str, code: &str, lang: &str) -> GraphQLRequest {
+        GraphQLRequest::new(
+            operation_name,
+            mutation,
+            json!({
+                "titleSlug": title_slug,
+                "code": code,
+                "lang": lang,
+            }),
+        )
     }
 
     /// Build filter object for GraphQL query
-    fn build_filter_object(&self, filters: &ProblemFilters) -> String {
-        let mut filter_parts = Vec::new();
+    fn build_filter_object(&self, filters: &ProblemFilters) -> serde_json::Value {
+        let mut filter_obj = serde_json::Map::new();
 
         if let Some(ref difficulty) = filters.difficulty {
             let difficulty_str = match difficulty {
@@ -557,28 +1083,111 @@ impl LeetCodeApi {
                 Difficulty::Medium => "MEDIUM", 
                 Difficulty::Hard => "HARD",
             };
-            filter_parts.push(format!(r#""difficulty": "{}""#, difficulty_str));
+            filter_obj.insert("difficulty".to_string(), json!(difficulty_str));
         }
 
         if !filters.tags.is_empty() {
-            let tags_json = serde_json::to_string(&filters.tags).unwrap_or_default();
-            filter_parts.push(format!(r#""tags": {}"#, tags_json));
+            filter_obj.insert("tags".to_string(), json!(filters.tags));
         }
 
         if let Some(ref company_tag) = filters.company_tag {
-            filter_parts.push(format!(r#""companyTag": "{}""#, company_tag));
+            filter_obj.insert("companyTag".to_string(), json!(company_tag));
         }
 
         if let Some(ref status) = filters.status {
-            filter_parts.push(format!(r#""status": "{}""#, status));
+            filter_obj.insert("status".to_string(), json!(status));
         }
 
-        if filter_parts.is_empty() {
-            "{}".to_string()
-        } else {
-            format!("{{{}}}", filter_parts.join(", "))
+        if let Some(ref list_id) = filters.list_id {
+            filter_obj.insert("listId".to_string(), json!(list_id));
+        }
+
+        if let Some(ref keywords) = filters.search_keywords {
+            filter_obj.insert("searchKeywords".to_string(), json!(keywords));
+        }
+
+        if let Some(sort_by) = filters.sort_by.filter(|_| self.site.supports_list_sorting()) {
+            filter_obj.insert("orderBy".to_string(), json!(sort_by.order_by()));
+            filter_obj.insert("sortOrder".to_string(), json!(filters.sort_order.sort_order()));
         }
+
+        if let Some(premium_only) = filters.premium_only {
+            filter_obj.insert("premiumOnly".to_string(), json!(premium_only));
+        }
+
+        serde_json::Value::Object(filter_obj)
+    }
+}
+
+/// Parse judge metrics such as `"3 ms"` or `"16.2 MB"`; `"N/A"` yields None
+fn parse_metric(value: &str) -> Option<f64> {
+    value.split_whitespace().next()?.parse::<f64>().ok()
+}
+
+/// Serialize a request body
+fn to_json<T: serde::Serialize>(value: &T) -> Result<String> {
+    serde_json::to_string(value)
+        .map_err(|e| LeetCodeError::Parse(format!("Failed to serialize request: {}", e)))
+}
+
+/// Deserialize a response body, naming `what` in the error
+fn parse_json<T: DeserializeOwned>(body: &str, what: &str) -> Result<T> {
+    serde_json::from_str(body)
+        .map_err(|e| LeetCodeError::Parse(format!("Failed to parse {}: {}", what, e)))
+}
+
+/// Deserialize a GraphQL response, classifying `errors` and requiring `data`
+fn parse_graphql<T: DeserializeOwned>(body: &str, what: &str) -> Result<T> {
+    let response: GraphQLResponse<T> = parse_json(body, what)?;
+
+    if let Some(errors) = response.errors {
+        return Err(LeetCodeError::from_graphql_errors(&errors));
+    }
+
+    response.data
+        .ok_or_else(|| LeetCodeError::Parse(format!("No data in {}", what)))
+}
+
+/// Whether a question detail response has the question but no content
+fn is_locked_question(body: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|json| json.pointer("/data/question").map(|question| question.is_object() && question["content"].is_null()))
+        .unwrap_or(false)
+}
+
+/// Whether a request can be safely repeated: GETs and GraphQL queries, not mutations
+fn is_idempotent(request: &HttpRequest) -> bool {
+    match request.method {
+        HttpMethod::Get => true,
+        HttpMethod::Post => request.url.ends_with("/graphql/")
+            && request.header_value("x-csrftoken").is_none(),
+    }
+}
+
+/// Whether a judge `state` means the run has not finished yet
+fn is_judge_pending(state: Option<&str>) -> bool {
+    matches!(state, Some("PENDING" | "STARTED"))
+}
+
+/// Add up to 25% of random jitter so concurrent pollers don't synchronize
+fn with_jitter(interval: Duration) -> Duration {
+    let fraction = f64::from(crate::crypto::random_u32()) / f64::from(u32::MAX);
+    interval + interval.mul_f64(fraction / 4.0)
+}
+
+/// Split newline-separated `data_input` into `count` testcases of equal line count
+///
+/// Falls back to a single testcase holding the whole input when lines don't divide evenly.
+fn split_testcases(data_input: &str, count: usize) -> Vec<String> {
+    let lines: Vec<&str> = data_input.lines().collect();
+    if count == 0 || lines.is_empty() || !lines.len().is_multiple_of(count) {
+        return vec![data_input.to_string()];
     }
+
+    lines.chunks(lines.len() / count)
+        .map(|chunk| chunk.join("\n"))
+        .collect()
 }
 
 impl Default for LeetCodeApi {
@@ -590,6 +1199,8 @@ impl Default for LeetCodeApi {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::{SortField, SortOrder};
+    use crate::transport::{HttpResponse, MockTransport};
 
     #[test]
     fn test_api_creation() {
@@ -606,41 +1217,102 @@ mod tests {
         let api = LeetCodeApi::new();
         let filters = ProblemFilters::default();
         let filter_obj = api.build_filter_object(&filters);
-        assert_eq!(filter_obj, "{}");
+        assert_eq!(filter_obj, json!({}));
     }
 
     #[test]
     fn test_filter_object_with_difficulty() {
         let api = LeetCodeApi::new();
-        let mut filters = ProblemFilters::default();
-        filters.difficulty = Some(Difficulty::Easy);
+        let filters = ProblemFilters {
+            difficulty: Some(Difficulty::Easy),
+            ..Default::default()
+        };
         
         let filter_obj = api.build_filter_object(&filters);
-        assert!(filter_obj.contains(r#""difficulty": "EASY""#));
+        assert_eq!(filter_obj["difficulty"], "EASY");
+    }
+
+    #[test]
+    fn test_filter_object_with_premium_only() {
+        let api = LeetCodeApi::new();
+        let filters = ProblemFilters {
+            premium_only: Some(false),
+            ..Default::default()
+        };
+
+        let filter_obj = api.build_filter_object(&filters);
+        assert_eq!(filter_obj["premiumOnly"], json!(false));
+    }
+
+    #[test]
+    fn test_filter_object_with_search_keywords() {
+        let api = LeetCodeApi::new();
+        let filters = ProblemFilters {
+            search_keywords: Some("lru cache".to_string()),
+            ..Default::default()
+        };
+
+        let filter_obj = api.build_filter_object(&filters);
+        assert_eq!(filter_obj["searchKeywords"], "lru cache");
+    }
+
+    #[test]
+    fn test_filter_object_with_status_and_list() {
+        let api = LeetCodeApi::new();
+        let filters = ProblemFilters {
+            status: Some("TRIED".to_string()),
+            list_id: Some("wpwgkgt".to_string()),
+            ..Default::default()
+        };
+
+        let filter_obj = api.build_filter_object(&filters);
+        assert_eq!(filter_obj["status"], "TRIED");
+        assert_eq!(filter_obj["listId"], "wpwgkgt");
+    }
+
+    #[test]
+    fn test_filter_object_with_sort() {
+        let filters = ProblemFilters {
+            sort_by: Some(SortField::Acceptance),
+            sort_order: SortOrder::Descending,
+            ..Default::default()
+        };
+
+        let filter_obj = LeetCodeApi::new().build_filter_object(&filters);
+        assert_eq!(filter_obj["orderBy"], "AC_RATE");
+        assert_eq!(filter_obj["sortOrder"], "DESCENDING");
+
+        // leetcode.cn results are sorted locally instead
+        let filter_obj = LeetCodeApi::new().with_site(Site::China).build_filter_object(&filters);
+        assert_eq!(filter_obj, json!({}));
     }
 
     #[test]
     fn test_filter_object_with_tags() {
         let api = LeetCodeApi::new();
-        let mut filters = ProblemFilters::default();
-        filters.tags = vec!["array".to_string(), "string".to_string()];
+        let filters = ProblemFilters {
+            tags: vec!["array".to_string(), "string".to_string()],
+            ..Default::default()
+        };
         
         let filter_obj = api.build_filter_object(&filters);
-        assert!(filter_obj.contains(r#""tags": ["array","string"]"#));
+        assert_eq!(filter_obj["tags"], json!(["array", "string"]));
     }
 
     #[test]
     fn test_filter_object_complex() {
         let api = LeetCodeApi::new();
-        let mut filters = ProblemFilters::default();
-        filters.difficulty = Some(Difficulty::Medium);
-        filters.tags = vec!["array".to_string()];
-        filters.company_tag = Some("google".to_string());
+        let filters = ProblemFilters {
+            difficulty: Some(Difficulty::Medium),
+            tags: vec!["array".to_string()],
+            company_tag: Some("google".to_string()),
+            ..Default::default()
+        };
         
         let filter_obj = api.build_filter_object(&filters);
-        assert!(filter_obj.contains(r#""difficulty": "MEDIUM""#));
-        assert!(filter_obj.contains(r#""tags": ["array"]"#));
-        assert!(filter_obj.contains(r#""companyTag": "google""#));
+        assert_eq!(filter_obj["difficulty"], "MEDIUM");
+        assert_eq!(filter_obj["tags"], json!(["array"]));
+        assert_eq!(filter_obj["companyTag"], "google");
     }
 
     #[test]
@@ -649,11 +1321,13 @@ mod tests {
         let filters = ProblemFilters::default();
         let query = api.build_problem_list_query(&filters);
         
-        assert!(query.contains("problemsetQuestionList"));
-        assert!(query.contains("questionFrontendId"));
-        assert!(query.contains("title"));
-        assert!(query.contains("difficulty"));
-        assert!(query.contains("topicTags"));
+        assert_eq!(query.operation_name, "problemsetQuestionList");
+        assert!(query.query.contains("questionFrontendId"));
+        assert!(query.query.contains("title"));
+        assert!(query.query.contains("difficulty"));
+        assert!(query.query.contains("topicTags"));
+        assert_eq!(query.variables["skip"], 0);
+        assert_eq!(query.variables["limit"], 50);
     }
 
     #[test]
@@ -661,11 +1335,207 @@ mod tests {
         let api = LeetCodeApi::new();
         let query = api.build_problem_detail_query("two-sum");
         
-        assert!(query.contains("question"));
-        assert!(query.contains("content"));
-        assert!(query.contains("codeSnippets"));
-        assert!(query.contains("sampleTestCase"));
-        assert!(query.contains(r#""titleSlug": "two-sum""#));
+        assert!(query.query.contains("question"));
+        assert!(query.query.contains("content"));
+        assert!(query.query.contains("codeSnippets"));
+        assert!(query.query.contains("sampleTestCase"));
+        assert_eq!(query.variables["titleSlug"], "two-sum");
+    }
+
+    #[test]
+    fn test_china_site_queries() {
+        let api = LeetCodeApi::new().with_site(Site::China);
+
+        let list_query = api.build_problem_list_query(&ProblemFilters::default());
+        assert!(list_query.query.contains("translatedTitle: titleCn"));
+
+        let detail_query = api.build_problem_detail_query("two-sum");
+        assert!(detail_query.query.contains("translatedContent"));
+
+        let request = api.build_graphql_request("{}".to_string(), Some("abc"));
+        assert_eq!(request.url, "https://leetcode.cn/graphql/");
+        assert_eq!(request.header_value("Cookie"), Some("LEETCODE_SESSION=abc"));
+    }
+
+    #[test]
+    fn test_authorize_mutation_headers() {
+        let api = LeetCodeApi::with_session("abc".to_string())
+            .with_csrf_token("token123".to_string());
+
+        let request = api.authorize_mutation(api.build_graphql_request("{}".to_string(), None), "two-sum").unwrap();
+        assert_eq!(request.header_value("Cookie"), Some("LEETCODE_SESSION=abc; csrftoken=token123"));
+        assert_eq!(request.header_value("x-csrftoken"), Some("token123"));
+        assert_eq!(request.header_value("Referer"), Some("https://leetcode.com/problems/two-sum/"));
+    }
+
+    #[test]
+    fn test_csrf_token_from_cookie_string() {
+        let api = LeetCodeApi::with_session("LEETCODE_SESSION=abc; csrftoken=xyz".to_string());
+        assert_eq!(api.csrf_token(), Some("xyz".to_string()));
+
+        let request = api.authorize_mutation(api.build_graphql_request("{}".to_string(), None), "two-sum").unwrap();
+        assert_eq!(request.header_value("Cookie"), Some("LEETCODE_SESSION=abc; csrftoken=xyz"));
+    }
+
+    #[test]
+    fn test_authorize_mutation_requires_csrf() {
+        let api = LeetCodeApi::with_session("abc".to_string());
+        let result = api.authorize_mutation(api.build_graphql_request("{}".to_string(), None), "two-sum");
+        assert_eq!(result.unwrap_err(), LeetCodeError::CsrfMismatch);
+    }
+
+    #[test]
+    fn test_parse_metric() {
+        assert_eq!(parse_metric("3 ms"), Some(3.0));
+        assert_eq!(parse_metric("16.2 MB"), Some(16.2));
+        assert_eq!(parse_metric("N/A"), None);
+    }
+
+    #[test]
+    fn test_parse_check_test_result_wrong_answer() {
+        let check = CheckResponse {
+            state: "SUCCESS".to_string(),
+            status_code: Some(10),
+            correct_answer: Some(false),
+            status_runtime: Some("0 ms".to_string()),
+            code_answer: vec!["[1,0]".to_string()],
+            expected_code_answer: vec!["[0,1]".to_string()],
+            std_output: Some("debug\n".to_string()),
+            total_correct: Some(0),
+            total_testcases: Some(1),
+            ..Default::default()
+        };
+
+        let result = LeetCodeApi::parse_check_test_result(check, "[2,7,11,15]\n9");
+        assert_eq!(result.status, TestStatus::WrongAnswer);
+        assert_eq!(result.runtime, Some(0));
+        assert_eq!(result.total_tests, 1);
+        assert_eq!(result.stdout.as_deref(), Some("debug\n"));
+        let failure = result.failed_test_case.unwrap();
+        assert!(failure.contains("Expected: [0,1]"));
+        assert!(failure.contains("Actual: [1,0]"));
+        assert!(!failure.contains("Stdout"));
+    }
+
+    #[test]
+    fn test_build_case_outcomes() {
+        let check = CheckResponse {
+            state: "SUCCESS".to_string(),
+            status_code: Some(10),
+            correct_answer: Some(false),
+            code_answer: vec!["[0,1]".to_string(), "[2,1]".to_string()],
+            expected_code_answer: vec!["[0,1]".to_string(), "[1,2]".to_string()],
+            compare_result: Some("10".to_string()),
+            std_output_list: vec!["".to_string(), "i=1\n".to_string(), "".to_string()],
+            total_correct: Some(1),
+            total_testcases: Some(2),
+            ..Default::default()
+        };
+
+        let result = LeetCodeApi::parse_check_test_result(check, "[2,7,11,15]\n9\n[3,2,4]\n6");
+        assert_eq!(result.passed_tests, 1);
+        assert_eq!(result.cases.len(), 2);
+        assert!(result.cases[0].passed);
+        assert_eq!(result.cases[0].input, "[2,7,11,15]\n9");
+        assert!(result.cases[0].stdout.is_none());
+        assert!(!result.cases[1].passed);
+        assert_eq!(result.cases[1].input, "[3,2,4]\n6");
+        assert_eq!(result.cases[1].expected, "[1,2]");
+        assert_eq!(result.cases[1].actual, "[2,1]");
+        assert_eq!(result.cases[1].stdout.as_deref(), Some("i=1\n"));
+    }
+
+    #[test]
+    fn test_with_jitter_bounds() {
+        let interval = Duration::from_millis(400);
+        let jittered = with_jitter(interval);
+        assert!(jittered >= interval);
+        assert!(jittered <= Duration::from_millis(500));
+        assert_eq!(with_jitter(Duration::ZERO), Duration::ZERO);
+
+        // Jitter doesn't depend on clock resolution
+        let samples: std::collections::HashSet<_> = (0..20).map(|_| with_jitter(Duration::from_secs(1))).collect();
+        assert!(samples.len() > 1);
+    }
+
+    #[test]
+    fn test_is_idempotent() {
+        let api = LeetCodeApi::with_session("abc".to_string()).with_csrf_token("token".to_string());
+        let query = api.build_graphql_request("{}".to_string(), Some("abc"));
+        assert!(is_idempotent(&query));
+        assert!(is_idempotent(&HttpRequest::get("https://leetcode.com/submissions/detail/1/check/")));
+
+        let mutation = api.authorize_mutation(api.build_graphql_request("{}".to_string(), None), "two-sum").unwrap();
+        assert!(!is_idempotent(&mutation));
+        assert!(!is_idempotent(&HttpRequest::post("https://leetcode.com/problems/two-sum/submit/", "{}".to_string())));
+    }
+
+    #[test]
+    fn test_retry_backoff_is_capped() {
+        let policy = RetryPolicy {
+            max_retries: 10,
+            initial_backoff: Duration::from_secs(1),
+            max_delay: Duration::from_secs(5),
+        };
+        assert!(policy.backoff(0) >= Duration::from_secs(1));
+        assert!(policy.backoff(0) <= Duration::from_millis(1250));
+        assert_eq!(policy.backoff(8), Duration::from_secs(5));
+        assert_eq!(policy.backoff(40), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_judge_pending_states() {
+        assert!(is_judge_pending(Some("PENDING")));
+        assert!(is_judge_pending(Some("STARTED")));
+        assert!(!is_judge_pending(Some("SUCCESS")));
+        assert!(!is_judge_pending(None));
+    }
+
+    #[test]
+    fn test_split_testcases() {
+        assert_eq!(split_testcases("[1]\n2\n[3]\n4", 2), vec!["[1]\n2", "[3]\n4"]);
+        assert_eq!(split_testcases("a\nb\nc", 2), vec!["a\nb\nc"]);
+        assert_eq!(split_testcases("", 1), vec![""]);
+    }
+
+    #[test]
+    fn test_parse_check_submission_compile_error() {
+        let check = CheckResponse {
+            state: "SUCCESS".to_string(),
+            status_code: Some(20),
+            compile_error: Some("Line 1: error".to_string()),
+            full_compile_error: Some("Line 1: error: expected `;`".to_string()),
+            ..Default::default()
+        };
+
+        let result = LeetCodeApi::parse_check_submission_result(check);
+        assert_eq!(result.status, SubmissionStatus::CompileError);
+        assert_eq!(result.compile_error.as_deref(), Some("Line 1: error: expected `;`"));
+    }
+
+    #[test]
+    fn test_code_mutation_round_trips_source() {
+        let code = "fn main() {\n\tlet s = \"a\\\\b\";\r\n    // 🦀 \\u{1F980} \u{0007}\n}";
+        let query = LeetCodeApi::build_code_mutation("submitSolution", SUBMIT_SOLUTION_MUTATION, "two-sum", code, "rust");
+
+        let body = serde_json::to_string(&query).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(parsed["variables"]["code"].as_str().unwrap(), code);
+        assert_eq!(parsed["operationName"], "submitSolution");
+    }
+
+    #[test]
+    fn test_filter_values_are_escaped() {
+        let api = LeetCodeApi::new();
+        let filters = ProblemFilters {
+            company_tag: Some("evil\", \"x\": \"y".to_string()),
+            ..Default::default()
+        };
+
+        let body = serde_json::to_string(&api.build_problem_list_query(&filters)).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(parsed["variables"]["filters"]["companyTag"], "evil\", \"x\": \"y");
+        assert!(parsed["variables"]["filters"].get("x").is_none());
     }
 
     #[test]
@@ -673,6 +1543,7 @@ mod tests {
         let api = LeetCodeApi::new();
         let mock_result = TestSubmissionResult {
             submission_id: "123".to_string(),
+            state: Some("SUCCESS".to_string()),
             status_code: 10,
             status: "Success".to_string(),
             runtime: Some("16".to_string()),
@@ -685,10 +1556,12 @@ mod tests {
             last_testcase: None,
             expected_output: None,
             code_output: None,
+            std_output: Some(String::new()),
         };
 
         let result = api.parse_test_result(mock_result);
         assert_eq!(result.status, TestStatus::Success);
+        assert!(result.stdout.is_none());
         assert_eq!(result.runtime, Some(16));
         assert_eq!(result.memory, Some(12.5));
         assert_eq!(result.passed_tests, 5);
@@ -700,6 +1573,7 @@ mod tests {
         let api = LeetCodeApi::new();
         let mock_result = TestSubmissionResult {
             submission_id: "123".to_string(),
+            state: Some("SUCCESS".to_string()),
             status_code: 11,
             status: "Wrong Answer".to_string(),
             runtime: None,
@@ -712,9 +1586,11 @@ mod tests {
             last_testcase: Some("[1,2,3]".to_string()),
             expected_output: Some("[1,3]".to_string()),
             code_output: Some("[1,2]".to_string()),
+            std_output: Some("visited 3\n".to_string()),
         };
 
         let result = api.parse_test_result(mock_result);
+        assert_eq!(result.stdout.as_deref(), Some("visited 3\n"));
         assert_eq!(result.status, TestStatus::WrongAnswer);
         assert_eq!(result.passed_tests, 2);
         assert_eq!(result.total_tests, 5);
@@ -729,7 +1605,7 @@ mod tests {
     #[test]
     fn test_run_test_requires_auth() {
         let api = LeetCodeApi::new(); // No session cookie
-        let result = api.run_test("two-sum", "test code", "python");
+        let result = api.run_test("two-sum", "test code", "python", "[2,7,11,15]\n9");
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("Authentication required"));
     }
@@ -739,6 +1615,7 @@ mod tests {
         let api = LeetCodeApi::new();
         let mock_details = SubmissionDetails {
             submission_id: "123".to_string(),
+            state: Some("SUCCESS".to_string()),
             status_code: 10,
             status: "Accepted".to_string(),
             runtime: Some("16".to_string()),
@@ -769,6 +1646,7 @@ mod tests {
         let api = LeetCodeApi::new();
         let mock_details = SubmissionDetails {
             submission_id: "123".to_string(),
+            state: Some("SUCCESS".to_string()),
             status_code: 11,
             status: "Wrong Answer".to_string(),
             runtime: None,
@@ -804,6 +1682,67 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("Authentication required"));
     }
 
+    #[test]
+    fn test_verify_authentication_with_mock_transport() {
+        let transport = MockTransport::with_responses([
+            r#"{"data": {"userStatus": {"isSignedIn": true, "username": "alice"}}}"#,
+            r#"{"data": {"userStatus": {"isSignedIn": false, "username": null}}}"#,
+        ]);
+        let api = LeetCodeApi::new().with_transport(transport.clone());
+
+        assert!(api.verify_authentication("LEETCODE_SESSION=valid").unwrap());
+        assert!(!api.verify_authentication("LEETCODE_SESSION=expired").unwrap());
+
+        let requests = transport.requests();
+        assert_eq!(requests[0].header_value("Cookie"), Some("LEETCODE_SESSION=valid"));
+        assert_eq!(requests[1].header_value("Cookie"), Some("LEETCODE_SESSION=expired"));
+    }
+
+    #[test]
+    fn test_verify_authentication_http_error() {
+        let transport = MockTransport::new();
+        transport.push(HttpResponse::new(403, "Forbidden"));
+        let api = LeetCodeApi::new().with_transport(transport);
+
+        assert!(!api.verify_authentication("LEETCODE_SESSION=valid").unwrap());
+    }
+
+    #[test]
+    fn test_verify_authentication_server_errors() {
+        let transport = MockTransport::new();
+        transport.push(HttpResponse::new(429, "Too Many Requests"));
+        transport.push(HttpResponse::new(503, "Service Unavailable"));
+        let api = LeetCodeApi::new()
+            .with_transport(transport)
+            .with_retry_policy(RetryPolicy::none());
+
+        assert!(matches!(
+            api.verify_authentication("LEETCODE_SESSION=valid"),
+            Err(LeetCodeError::RateLimited { .. })
+        ));
+        assert!(matches!(api.verify_authentication("LEETCODE_SESSION=valid"), Err(LeetCodeError::Network(_))));
+    }
+
+    #[test]
+    fn test_fetch_user_status() {
+        let transport = MockTransport::with_responses([
+            r#"{"data": {"userStatus": {"isSignedIn": true, "username": "alice", "isPremium": true,
+                "avatar": "https://assets.leetcode.com/alice.png", "realName": "Alice Liddell"}}}"#,
+            r#"{"data": {"userStatus": {"isSignedIn": false, "username": null}}}"#,
+            "not json",
+        ]);
+        let api = LeetCodeApi::new().with_transport(transport);
+
+        let status = api.fetch_user_status("LEETCODE_SESSION=valid").unwrap();
+        assert!(status.is_signed_in);
+        assert_eq!(status.username.as_deref(), Some("alice"));
+        assert_eq!(status.is_premium, Some(true));
+        assert_eq!(status.avatar.as_deref(), Some("https://assets.leetcode.com/alice.png"));
+        assert_eq!(status.real_name.as_deref(), Some("Alice Liddell"));
+        assert_eq!(api.fetch_user_status("LEETCODE_SESSION=expired").unwrap(), UserStatus::default());
+        assert!(matches!(api.fetch_user_status("LEETCODE_SESSION=valid"), Err(LeetCodeError::Parse(_))));
+    }
+
     // Note: Integration tests for actual API calls would be in tests/integration_tests.rs
     // to avoid real network calls in unit tests
 }
@@ -1,37 +1,333 @@
-use anyhow::{Context, Result};
-use std::path::PathBuf;
+use crate::api::LeetCodeApi;
+use crate::crypto::{self, SecretKey};
+use crate::error::LeetCodeError;
+use crate::models::Site;
+use anyhow::{anyhow, bail, Context, Result};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::fs;
+use std::process::Command;
+use std::time::Duration;
 use serde::{Deserialize, Serialize};
 use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Profile used when none is named
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// On-disk layout of auth.json; credential values are encrypted
+#[derive(Debug, Default, Serialize, Deserialize)]
 struct AuthConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    active_profile: Option<String>,
+    #[serde(default)]
+    profiles: BTreeMap<String, ProfileConfig>,
+    /// Single-account layout written before profiles existed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    session_cookie: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    csrf_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    created_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ProfileConfig {
     session_cookie: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    csrf_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    site: Option<Site>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    username: Option<String>,
     created_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    verified_at: Option<String>,
+}
+
+/// A saved LeetCode account
+#[derive(Debug, Clone, PartialEq)]
+pub struct Profile {
+    pub name: String,
+    pub session_cookie: String,
+    pub csrf_token: Option<String>,
+    /// `None` for sessions saved before profiles existed; the workspace site applies
+    pub site: Option<Site>,
+    pub username: Option<String>,
+    pub created_at: String,
+    /// When LeetCode last confirmed the session (RFC 3339)
+    pub verified_at: Option<String>,
+}
+
+impl Profile {
+    pub fn new(name: &str, session_cookie: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            session_cookie: session_cookie.to_string(),
+            csrf_token: None,
+            site: None,
+            username: None,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            verified_at: None,
+        }
+    }
+
+    /// Whether the session was confirmed less than `window` ago
+    pub fn verified_within(&self, window: Duration) -> bool {
+        let Some(verified_at) = self.verified_at.as_deref()
+            .and_then(|timestamp| chrono::DateTime::parse_from_rfc3339(timestamp).ok())
+        else {
+            return false;
+        };
+
+        chrono::Utc::now().signed_duration_since(verified_at)
+            .to_std()
+            .is_ok_and(|age| age < window)
+    }
+}
+
+/// Decrypted contents of auth.json
+#[derive(Debug, Default)]
+struct ProfileStore {
+    active_profile: Option<String>,
+    profiles: BTreeMap<String, Profile>,
+    /// Profiles that could not be decrypted, written back as saved so
+    /// updating other profiles never loses them
+    unreadable: BTreeMap<String, (ProfileConfig, anyhow::Error)>,
+}
+
+impl ProfileStore {
+    fn contains(&self, name: &str) -> bool {
+        self.profiles.contains_key(name) || self.unreadable.contains_key(name)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.profiles.is_empty() && self.unreadable.is_empty()
+    }
+
+    /// Remove `name`, returning whether it existed
+    fn remove(&mut self, name: &str) -> bool {
+        self.profiles.remove(name).is_some() | self.unreadable.remove(name).is_some()
+    }
+
+    /// First profile name in order, readable or not
+    fn first_name(&self) -> Option<String> {
+        self.profiles.keys().chain(self.unreadable.keys()).min().cloned()
+    }
+
+    /// Take the decrypted profile `name`, failing if it could not be decrypted
+    fn take(&mut self, name: &str) -> Result<Option<Profile>> {
+        if let Some((_, error)) = self.unreadable.remove(name) {
+            return Err(error.context(format!("Failed to load profile '{}'", name)));
+        }
+        Ok(self.profiles.remove(name))
+    }
 }
 
 pub struct AuthManager {
     config_path: PathBuf,
+    /// `None` when there is no per-user config directory to keep the key in
+    key_path: Option<PathBuf>,
+    /// auth.json written by older versions inside the workspace
+    legacy_path: Option<PathBuf>,
+    allow_git_tracked: bool,
+    /// How long a successful verification is trusted without asking LeetCode
+    verification_window: Duration,
 }
 
 impl AuthManager {
-    pub fn new(config_dir: &PathBuf) -> Self {
+    /// Credentials live in `config_dir/auth.json`, encrypted with the
+    /// machine-local key from `crypto::default_key_path`
+    ///
+    /// The key is never kept next to the credentials: without a per-user
+    /// config directory, saving and decrypting fail instead.
+    pub fn new(config_dir: &Path) -> Self {
         let config_path = config_dir.join("auth.json");
-        Self { config_path }
+        let key_path = crypto::default_key_path();
+        Self {
+            config_path,
+            key_path,
+            legacy_path: None,
+            allow_git_tracked: false,
+            verification_window: Duration::ZERO,
+        }
+    }
+
+    /// Default per-user credentials directory, outside any worktree
+    pub fn default_config_dir() -> Option<PathBuf> {
+        crypto::user_config_dir()
+    }
+
+    /// Use a different encryption key file
+    pub fn with_key_path(mut self, key_path: PathBuf) -> Self {
+        self.key_path = Some(key_path);
+        self
+    }
+
+    /// Location of the encryption key
+    fn key_path(&self) -> Result<&Path> {
+        self.key_path.as_deref().ok_or_else(|| {
+            anyhow!("No per-user config directory for the encryption key; set XDG_CONFIG_HOME or HOME")
+        })
     }
 
-    /// Save session cookie with basic encoding
+    /// Also read credentials from `legacy_dir/auth.json`, moving them to
+    /// the configured location the first time they are loaded
+    pub fn with_legacy_dir(mut self, legacy_dir: &Path) -> Self {
+        let legacy_path = legacy_dir.join("auth.json");
+        if legacy_path != self.config_path {
+            self.legacy_path = Some(legacy_path);
+        }
+        self
+    }
+
+    /// Allow saving credentials into a directory git would pick up
+    pub fn allow_git_tracked(mut self, allow: bool) -> Self {
+        self.allow_git_tracked = allow;
+        self
+    }
+
+    /// Trust a successful verification for `window` before checking again
+    pub fn with_verification_window(mut self, window: Duration) -> Self {
+        self.verification_window = window;
+        self
+    }
+
+    /// Where credentials are saved
+    pub fn config_path(&self) -> &Path {
+        &self.config_path
+    }
+
+    /// Save session cookie for the active profile, encrypted at rest
     pub fn save_session(&self, session_cookie: &str) -> Result<()> {
+        self.save_credentials(session_cookie, None)
+    }
+
+    /// Save session cookie and CSRF token for the active profile, encrypted at rest
+    ///
+    /// The profile keeps its site and username; `default` is used when no
+    /// profile is active.
+    pub fn save_credentials(&self, session_cookie: &str, csrf_token: Option<&str>) -> Result<()> {
+        let store = self.load_store()?;
+        let name = store.active_profile.as_deref().unwrap_or(DEFAULT_PROFILE);
+
+        let mut profile = Profile::new(name, session_cookie);
+        profile.csrf_token = csrf_token.map(str::to_string);
+        if let Some(existing) = store.profiles.get(name) {
+            profile.site = existing.site;
+            profile.username = existing.username.clone();
+        }
+        self.save_profile(profile)
+    }
+
+    /// Save `profile`, replacing any profile with the same name, and make it active
+    ///
+    /// Other profiles are kept as saved, including ones that can't be
+    /// decrypted with the current key.
+    pub fn save_profile(&self, profile: Profile) -> Result<()> {
+        let mut store = self.load_store()?;
+        store.unreadable.remove(&profile.name);
+        store.active_profile = Some(profile.name.clone());
+        store.profiles.insert(profile.name.clone(), profile);
+        self.write_store(&store)
+    }
+
+    /// Profile used by commands, if any
+    pub fn active_profile(&self) -> Result<Option<Profile>> {
+        let mut store = self.load_store()?;
+        match store.active_profile.take() {
+            Some(name) => store.take(&name),
+            None => Ok(None),
+        }
+    }
+
+    /// Saved profile called `name`
+    pub fn load_profile(&self, name: &str) -> Result<Option<Profile>> {
+        self.load_store()?.take(name)
+    }
+
+    /// All saved profiles that can be decrypted, ordered by name
+    pub fn list_profiles(&self) -> Result<Vec<Profile>> {
+        Ok(self.load_store()?.profiles.into_values().collect())
+    }
+
+    /// Make the saved profile `name` active
+    pub fn use_profile(&self, name: &str) -> Result<()> {
+        let mut store = self.load_store()?;
+        if !store.contains(name) {
+            bail!("No profile named '{}'", name);
+        }
+        store.active_profile = Some(name.to_string());
+        self.write_store(&store)
+    }
+
+    /// Delete the profile `name`, returning whether it existed
+    ///
+    /// Removing the active profile activates the first remaining one.
+    pub fn remove_profile(&self, name: &str) -> Result<bool> {
+        let mut store = self.load_store()?;
+        if !store.remove(name) {
+            return Ok(false);
+        }
+        if store.active_profile.as_deref() == Some(name) {
+            store.active_profile = store.first_name();
+        }
+        self.write_store(&store)?;
+        Ok(true)
+    }
+
+    /// Load and decrypt the active profile's session cookie
+    pub fn load_session(&self) -> Result<Option<String>> {
+        Ok(self.active_profile()?.map(|profile| profile.session_cookie))
+    }
+
+    /// Load and decrypt the CSRF token saved with the active profile
+    pub fn load_csrf_token(&self) -> Result<Option<String>> {
+        Ok(self.active_profile()?.and_then(|profile| profile.csrf_token))
+    }
+
+    /// Encrypt and write `store`, removing auth.json once no profiles are left
+    fn write_store(&self, store: &ProfileStore) -> Result<()> {
+        if store.is_empty() {
+            return self.logout();
+        }
+
+        // The key is as sensitive as the credentials it decrypts
+        let key_path = self.key_path()?;
+        if !self.allow_git_tracked
+            && let Some(tracked) = [self.config_path.as_path(), key_path].into_iter().find(|path| is_git_tracked(path))
+        {
+            bail!(
+                "Refusing to save credentials to {} because it is inside a git repository and not ignored. \
+                 Add it to .gitignore, map this workspace to another directory in `credentials_dirs`, \
+                 or set `allow_credentials_in_git` to true in ~/.config/zed-leetcode/config.json",
+                tracked.display()
+            );
+        }
+
         // Create config directory if it doesn't exist
         if let Some(parent) = self.config_path.parent() {
             fs::create_dir_all(parent)
                 .context("Failed to create config directory")?;
         }
 
-        let auth_config = AuthConfig {
-            session_cookie: BASE64.encode(session_cookie),
-            created_at: chrono::Utc::now().to_rfc3339(),
+        let key = SecretKey::load_or_create(key_path)?;
+        let mut auth_config = AuthConfig {
+            active_profile: store.active_profile.clone(),
+            ..AuthConfig::default()
         };
+        for (name, profile) in &store.profiles {
+            auth_config.profiles.insert(name.clone(), ProfileConfig {
+                session_cookie: key.encrypt(&profile.session_cookie)?,
+                csrf_token: profile.csrf_token.as_deref().map(|token| key.encrypt(token)).transpose()?,
+                site: profile.site,
+                username: profile.username.clone(),
+                created_at: profile.created_at.clone(),
+                verified_at: profile.verified_at.clone(),
+            });
+        }
+        for (name, (profile, _)) in &store.unreadable {
+            auth_config.profiles.insert(name.clone(), profile.clone());
+        }
 
         let config_json = serde_json::to_string_pretty(&auth_config)
             .context("Failed to serialize auth config")?;
@@ -51,50 +347,179 @@ impl AuthManager {
         Ok(())
     }
 
-    /// Load and decode session cookie
-    pub fn load_session(&self) -> Result<Option<String>> {
-        if !self.config_path.exists() {
+    /// Read and decrypt auth.json, or an empty store if there is none
+    ///
+    /// Files written by older versions (base64-encoded credentials, the
+    /// single-account layout, or auth.json inside the workspace) are
+    /// re-saved in the current format the first time they are read.
+    fn load_store(&self) -> Result<ProfileStore> {
+        let (auth_config, from_legacy_path) = match self.load_auth_config(&self.config_path)? {
+            Some(auth_config) => (auth_config, false),
+            None => match self.legacy_path.as_deref().map(|path| self.load_auth_config(path)).transpose()?.flatten() {
+                Some(auth_config) => (auth_config, true),
+                None => return Ok(ProfileStore::default()),
+            },
+        };
+
+        let mut profiles: Vec<_> = auth_config.profiles.into_iter().collect();
+        let mut active_profile = auth_config.active_profile;
+        let legacy_layout = auth_config.session_cookie.is_some();
+        if let Some(session_cookie) = auth_config.session_cookie {
+            if !profiles.iter().any(|(name, _)| name == DEFAULT_PROFILE) {
+                profiles.push((DEFAULT_PROFILE.to_string(), ProfileConfig {
+                    session_cookie,
+                    csrf_token: auth_config.csrf_token,
+                    site: None,
+                    username: None,
+                    created_at: auth_config.created_at.unwrap_or_else(|| chrono::Utc::now().to_rfc3339()),
+                    verified_at: None,
+                }));
+            }
+            active_profile.get_or_insert_with(|| DEFAULT_PROFILE.to_string());
+        }
+
+        let key = match self.key_path.as_deref() {
+            Some(key_path) => SecretKey::load(key_path)?,
+            None => None,
+        };
+        // The single-account layout is always rewritten
+        let mut needs_migration = from_legacy_path || legacy_layout;
+        let mut reveal = |value: &str| -> Result<String> {
+            if !crypto::is_encrypted(value) {
+                needs_migration = true;
+                return Self::decode(value);
+            }
+            key.as_ref()
+                .ok_or_else(|| anyhow!("Encryption key not found; please login again"))?
+                .decrypt(value)
+        };
+        let mut reveal_profile = |name: &str, profile: &ProfileConfig| -> Result<Profile> {
+            Ok(Profile {
+                name: name.to_string(),
+                session_cookie: reveal(&profile.session_cookie)?,
+                csrf_token: profile.csrf_token.as_deref().map(&mut reveal).transpose()?,
+                site: profile.site,
+                username: profile.username.clone(),
+                created_at: profile.created_at.clone(),
+                verified_at: profile.verified_at.clone(),
+            })
+        };
+
+        let mut store = ProfileStore { active_profile, ..ProfileStore::default() };
+        for (name, profile) in profiles {
+            match reveal_profile(&name, &profile) {
+                Ok(revealed) => {
+                    store.profiles.insert(name, revealed);
+                },
+                Err(error) => {
+                    store.unreadable.insert(name, (profile, error));
+                },
+            }
+        }
+
+        // Migration is best effort: the loaded credentials are still usable
+        if needs_migration && self.write_store(&store).is_ok() && from_legacy_path
+            && let Some(legacy_path) = &self.legacy_path
+        {
+            let _ = fs::remove_file(legacy_path);
+        }
+
+        Ok(store)
+    }
+
+    /// Read an auth.json if it exists
+    fn load_auth_config(&self, path: &Path) -> Result<Option<AuthConfig>> {
+        if !path.exists() {
             return Ok(None);
         }
 
-        let config_content = fs::read_to_string(&self.config_path)
+        let config_content = fs::read_to_string(path)
             .context("Failed to read auth config")?;
 
         let auth_config: AuthConfig = serde_json::from_str(&config_content)
             .context("Failed to parse auth config")?;
 
-        let decoded_cookie = BASE64.decode(&auth_config.session_cookie)
-            .context("Failed to decode session cookie")?;
+        Ok(Some(auth_config))
+    }
 
-        let cookie_str = String::from_utf8(decoded_cookie)
-            .context("Invalid UTF-8 in decoded cookie")?;
+    /// Decode a legacy base64-encoded credential
+    fn decode(encoded: &str) -> Result<String> {
+        let decoded = BASE64.decode(encoded)
+            .context("Failed to decode stored credential")?;
 
-        Ok(Some(cookie_str))
+        String::from_utf8(decoded)
+            .context("Invalid UTF-8 in decoded credential")
     }
 
-    /// Verify session by making an authenticated API call
-    pub fn verify_session(&self, api_client: &crate::api::LeetCodeApi) -> Result<bool> {
-        if let Some(session_cookie) = self.load_session()? {
-            // Try to fetch user profile to verify session
-            match api_client.verify_authentication(&session_cookie) {
-                Ok(is_valid) => Ok(is_valid),
-                Err(_) => Ok(false),
-            }
-        } else {
-            Ok(false)
+    /// Verify the active profile's session
+    ///
+    /// A verification younger than the verification window is trusted
+    /// without a request. Otherwise LeetCode is asked: a successful answer
+    /// is recorded and a signed-out one clears the earlier verification. If
+    /// LeetCode can't be reached, is rate limiting or fails, a session that
+    /// was verified before is still trusted so the real request decides.
+    pub fn verify_session(&self, api_client: &LeetCodeApi) -> Result<bool> {
+        let Some(profile) = self.active_profile()? else {
+            return Ok(false);
+        };
+        if profile.verified_within(self.verification_window) {
+            return Ok(true);
+        }
+
+        match api_client.fetch_user_status(&profile.session_cookie) {
+            Ok(user_status) if user_status.is_signed_in => {
+                // Only caching depends on this write
+                let _ = self.record_verification(&profile.name, user_status.username);
+                Ok(true)
+            },
+            Ok(_) | Err(LeetCodeError::Unauthenticated) => {
+                let _ = self.forget_verification();
+                Ok(false)
+            },
+            // Rate limited, server or network trouble says nothing about the
+            // session, so keep trusting an earlier verification
+            Err(_) => Ok(profile.verified_at.is_some()),
         }
     }
 
     /// Check if user is currently authenticated
-    pub fn is_authenticated(&self, api_client: &crate::api::LeetCodeApi) -> bool {
+    pub fn is_authenticated(&self, api_client: &LeetCodeApi) -> bool {
         self.verify_session(api_client).unwrap_or(false)
     }
 
-    /// Clear stored authentication
+    /// Record that LeetCode accepted the session of profile `name`
+    pub fn record_verification(&self, name: &str, username: Option<String>) -> Result<()> {
+        let mut store = self.load_store()?;
+        let Some(profile) = store.profiles.get_mut(name) else {
+            return Ok(());
+        };
+        profile.verified_at = Some(chrono::Utc::now().to_rfc3339());
+        if username.is_some() {
+            profile.username = username;
+        }
+        self.write_store(&store)
+    }
+
+    /// Stop trusting the active profile's last verification, e.g. after
+    /// LeetCode rejected its session
+    pub fn forget_verification(&self) -> Result<()> {
+        let mut store = self.load_store()?;
+        let Some(profile) = store.active_profile.as_ref().and_then(|name| store.profiles.get_mut(name)) else {
+            return Ok(());
+        };
+        if profile.verified_at.take().is_some() {
+            self.write_store(&store)?;
+        }
+        Ok(())
+    }
+
+    /// Clear all stored profiles, including any legacy workspace copy
     pub fn logout(&self) -> Result<()> {
-        if self.config_path.exists() {
-            fs::remove_file(&self.config_path)
-                .context("Failed to remove auth config")?;
+        for path in std::iter::once(&self.config_path).chain(&self.legacy_path) {
+            if path.exists() {
+                fs::remove_file(path)
+                    .context("Failed to remove auth config")?;
+            }
         }
         Ok(())
     }
@@ -105,15 +530,32 @@ impl AuthManager {
     }
 }
 
+/// Whether git would pick up `path`: inside a work tree and not ignored
+///
+/// If git itself cannot be run the file is assumed not to be ignored.
+fn is_git_tracked(path: &Path) -> bool {
+    let Some(repo_root) = path.ancestors().skip(1).find(|dir| dir.join(".git").exists()) else {
+        return false;
+    };
+
+    match Command::new("git").arg("-C").arg(repo_root).args(["check-ignore", "-q"]).arg(path).output() {
+        Ok(output) => !output.status.success(),
+        Err(_) => true,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::api::RetryPolicy;
+    use crate::transport::{HttpResponse, MockTransport};
     use tempfile::TempDir;
 
     fn create_test_auth_manager() -> (AuthManager, TempDir) {
         let temp_dir = TempDir::new().unwrap();
         let config_dir = temp_dir.path().to_path_buf();
-        let auth_manager = AuthManager::new(&config_dir);
+        let auth_manager = AuthManager::new(&config_dir)
+            .with_key_path(config_dir.join("keys").join("secret.key"));
         (auth_manager, temp_dir)
     }
 
@@ -131,6 +573,19 @@ mod tests {
         assert_eq!(loaded_session.unwrap(), test_cookie);
     }
 
+    #[test]
+    fn test_save_and_load_csrf_token() {
+        let (auth_manager, _temp_dir) = create_test_auth_manager();
+
+        auth_manager.save_credentials("LEETCODE_SESSION=abc", Some("csrf123")).unwrap();
+        assert_eq!(auth_manager.load_session().unwrap().unwrap(), "LEETCODE_SESSION=abc");
+        assert_eq!(auth_manager.load_csrf_token().unwrap().unwrap(), "csrf123");
+
+        // Sessions saved without a token (or by older versions) have none
+        auth_manager.save_session("LEETCODE_SESSION=abc").unwrap();
+        assert!(auth_manager.load_csrf_token().unwrap().is_none());
+    }
+
     #[test]
     fn test_load_nonexistent_session() {
         let (auth_manager, _temp_dir) = create_test_auth_manager();
@@ -195,4 +650,267 @@ mod tests {
         let result = auth_manager.load_session();
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_session_is_encrypted_at_rest() {
+        let (auth_manager, _temp_dir) = create_test_auth_manager();
+        let test_cookie = "LEETCODE_SESSION=test_session_value";
+
+        auth_manager.save_credentials(test_cookie, Some("csrf123")).unwrap();
+
+        let contents = fs::read_to_string(&auth_manager.config_path).unwrap();
+        assert!(!contents.contains(test_cookie));
+        assert!(!contents.contains(&BASE64.encode(test_cookie)));
+        assert!(!contents.contains("csrf123"));
+        assert_eq!(auth_manager.load_session().unwrap().unwrap(), test_cookie);
+    }
+
+    #[test]
+    fn test_legacy_base64_config_is_migrated() {
+        let (auth_manager, _temp_dir) = create_test_auth_manager();
+        let legacy_config = format!(
+            r#"{{"session_cookie": "{}", "csrf_token": "{}", "created_at": "2023-01-01T00:00:00Z"}}"#,
+            BASE64.encode("LEETCODE_SESSION=legacy"),
+            BASE64.encode("csrf123"),
+        );
+        fs::write(&auth_manager.config_path, legacy_config).unwrap();
+
+        assert_eq!(auth_manager.load_session().unwrap().unwrap(), "LEETCODE_SESSION=legacy");
+
+        let migrated: AuthConfig = serde_json::from_str(&fs::read_to_string(&auth_manager.config_path).unwrap()).unwrap();
+        assert!(migrated.session_cookie.is_none());
+        assert_eq!(migrated.active_profile.as_deref(), Some(DEFAULT_PROFILE));
+        let profile = &migrated.profiles[DEFAULT_PROFILE];
+        assert!(crypto::is_encrypted(&profile.session_cookie));
+        assert!(crypto::is_encrypted(profile.csrf_token.as_deref().unwrap()));
+        assert_eq!(profile.created_at, "2023-01-01T00:00:00Z");
+        assert_eq!(auth_manager.load_csrf_token().unwrap().unwrap(), "csrf123");
+    }
+
+    fn test_profile(name: &str, site: Site) -> Profile {
+        let mut profile = Profile::new(name, &format!("LEETCODE_SESSION={}", name));
+        profile.csrf_token = Some(format!("csrf-{}", name));
+        profile.site = Some(site);
+        profile.username = Some(format!("{}-user", name));
+        profile
+    }
+
+    #[test]
+    fn test_profiles() {
+        let (auth_manager, _temp_dir) = create_test_auth_manager();
+        assert!(auth_manager.active_profile().unwrap().is_none());
+
+        auth_manager.save_profile(test_profile("personal", Site::Global)).unwrap();
+        let work = test_profile("work", Site::China);
+        auth_manager.save_profile(work.clone()).unwrap();
+
+        // The last saved profile becomes active
+        assert_eq!(auth_manager.active_profile().unwrap().unwrap(), work);
+        assert_eq!(auth_manager.load_session().unwrap().unwrap(), "LEETCODE_SESSION=work");
+
+        let names: Vec<_> = auth_manager.list_profiles().unwrap().into_iter().map(|profile| profile.name).collect();
+        assert_eq!(names, ["personal", "work"]);
+
+        auth_manager.use_profile("personal").unwrap();
+        assert_eq!(auth_manager.load_csrf_token().unwrap().unwrap(), "csrf-personal");
+        assert!(auth_manager.use_profile("missing").is_err());
+
+        // Saving a session keeps the active profile's site and username
+        auth_manager.save_session("LEETCODE_SESSION=renewed").unwrap();
+        let renewed = auth_manager.load_profile("personal").unwrap().unwrap();
+        assert_eq!(renewed.session_cookie, "LEETCODE_SESSION=renewed");
+        assert_eq!(renewed.site, Some(Site::Global));
+        assert_eq!(renewed.username.as_deref(), Some("personal-user"));
+        assert!(renewed.csrf_token.is_none());
+    }
+
+    #[test]
+    fn test_remove_profile() {
+        let (auth_manager, _temp_dir) = create_test_auth_manager();
+        auth_manager.save_profile(test_profile("personal", Site::Global)).unwrap();
+        auth_manager.save_profile(test_profile("work", Site::China)).unwrap();
+
+        assert!(!auth_manager.remove_profile("missing").unwrap());
+        assert!(auth_manager.remove_profile("work").unwrap());
+        assert_eq!(auth_manager.active_profile().unwrap().unwrap().name, "personal");
+
+        assert!(auth_manager.remove_profile("personal").unwrap());
+        assert!(auth_manager.active_profile().unwrap().is_none());
+        assert!(!auth_manager.config_path.exists());
+    }
+
+    #[test]
+    fn test_missing_key_fails_to_load() {
+        let (auth_manager, temp_dir) = create_test_auth_manager();
+        auth_manager.save_session("LEETCODE_SESSION=abc").unwrap();
+
+        let other_key = AuthManager::new(temp_dir.path())
+            .with_key_path(temp_dir.path().join("missing.key"));
+        assert!(other_key.load_session().is_err());
+    }
+
+    #[test]
+    fn test_save_without_key_directory_fails() {
+        let (mut auth_manager, _temp_dir) = create_test_auth_manager();
+        auth_manager.key_path = None;
+
+        let error = auth_manager.save_session("LEETCODE_SESSION=abc").unwrap_err();
+        assert!(error.to_string().contains("XDG_CONFIG_HOME"));
+        assert!(!auth_manager.config_path.exists());
+        assert!(!auth_manager.config_path.with_file_name("secret.key").exists());
+    }
+
+    #[test]
+    fn test_legacy_workspace_credentials_are_moved() {
+        let (auth_manager, temp_dir) = create_test_auth_manager();
+        let workspace_dir = temp_dir.path().join("workspace").join(".leetcode");
+        let legacy_manager = AuthManager::new(&workspace_dir)
+            .with_key_path(temp_dir.path().join("keys").join("secret.key"));
+        legacy_manager.save_credentials("LEETCODE_SESSION=abc", Some("csrf123")).unwrap();
+
+        let auth_manager = auth_manager.with_legacy_dir(&workspace_dir);
+        assert_eq!(auth_manager.load_session().unwrap().unwrap(), "LEETCODE_SESSION=abc");
+        assert!(auth_manager.config_path.exists());
+        assert!(!workspace_dir.join("auth.json").exists());
+        assert_eq!(auth_manager.load_csrf_token().unwrap().unwrap(), "csrf123");
+    }
+
+    #[test]
+    fn test_refuses_to_save_into_git_repository() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path().join("repo");
+        let outside = temp_dir.path().join("outside");
+        if Command::new("git").arg("init").arg("-q").arg(&repo).status().is_err() {
+            return;
+        }
+        let repo_key = repo.join("keys").join("secret.key");
+        let outside_key = outside.join("secret.key");
+
+        let tracked = AuthManager::new(&repo.join(".leetcode")).with_key_path(outside_key.clone());
+        let error = tracked.save_session("LEETCODE_SESSION=abc").unwrap_err();
+        assert!(error.to_string().contains("allow_credentials_in_git"));
+        assert!(!tracked.config_path.exists());
+
+        // The key must not end up in the repository either
+        let tracked_key = AuthManager::new(&outside).with_key_path(repo_key.clone());
+        let error = tracked_key.save_session("LEETCODE_SESSION=abc").unwrap_err();
+        assert!(error.to_string().contains("secret.key"));
+        assert!(!repo_key.exists());
+
+        // Explicitly allowed
+        let allowed = AuthManager::new(&repo.join(".leetcode"))
+            .with_key_path(repo_key.clone())
+            .allow_git_tracked(true);
+        assert!(allowed.save_session("LEETCODE_SESSION=abc").is_ok());
+
+        // Ignored by the repository
+        fs::write(repo.join(".gitignore"), "secrets/\nkeys/\n").unwrap();
+        let ignored = AuthManager::new(&repo.join("secrets")).with_key_path(repo_key);
+        assert!(ignored.save_session("LEETCODE_SESSION=abc").is_ok());
+    }
+
+    fn api_with(transport: &MockTransport) -> LeetCodeApi {
+        LeetCodeApi::new()
+            .with_transport(transport.clone())
+            .with_retry_policy(RetryPolicy::none())
+    }
+
+    fn verified_profile(age: chrono::Duration) -> Profile {
+        let mut profile = Profile::new(DEFAULT_PROFILE, "LEETCODE_SESSION=abc");
+        profile.verified_at = Some((chrono::Utc::now() - age).to_rfc3339());
+        profile
+    }
+
+    #[test]
+    fn test_recent_verification_skips_request() {
+        let (auth_manager, _temp_dir) = create_test_auth_manager();
+        let auth_manager = auth_manager.with_verification_window(Duration::from_secs(3600));
+        auth_manager.save_profile(verified_profile(chrono::Duration::minutes(5))).unwrap();
+
+        let transport = MockTransport::new();
+        assert!(auth_manager.verify_session(&api_with(&transport)).unwrap());
+        assert!(transport.requests().is_empty());
+    }
+
+    #[test]
+    fn test_stale_verification_is_refreshed() {
+        let (auth_manager, _temp_dir) = create_test_auth_manager();
+        let auth_manager = auth_manager.with_verification_window(Duration::from_secs(3600));
+        auth_manager.save_profile(verified_profile(chrono::Duration::hours(2))).unwrap();
+
+        let transport = MockTransport::with_responses([
+            r#"{"data": {"userStatus": {"isSignedIn": true, "username": "alice"}}}"#,
+        ]);
+        assert!(auth_manager.verify_session(&api_with(&transport)).unwrap());
+        assert_eq!(transport.requests().len(), 1);
+
+        let profile = auth_manager.active_profile().unwrap().unwrap();
+        assert!(profile.verified_within(Duration::from_secs(60)));
+        assert_eq!(profile.username.as_deref(), Some("alice"));
+
+        // The refreshed verification is trusted by the next command
+        assert!(auth_manager.verify_session(&api_with(&transport)).unwrap());
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[test]
+    fn test_verification_failures() {
+        let (auth_manager, _temp_dir) = create_test_auth_manager();
+        auth_manager.save_profile(verified_profile(chrono::Duration::hours(2))).unwrap();
+
+        // Offline: a previously verified session is still trusted
+        let transport = MockTransport::new();
+        transport.push_error("connection refused");
+        assert!(auth_manager.verify_session(&api_with(&transport)).unwrap());
+
+        // Rate limited or server errors don't drop the trust either
+        let transport = MockTransport::new();
+        transport.push(HttpResponse::new(429, "Too Many Requests"));
+        transport.push(HttpResponse::new(503, "Service Unavailable"));
+        assert!(auth_manager.verify_session(&api_with(&transport)).unwrap());
+        assert!(auth_manager.verify_session(&api_with(&transport)).unwrap());
+
+        // Rejected by LeetCode: the old verification is cleared
+        let transport = MockTransport::with_responses([r#"{"data": {"userStatus": {"isSignedIn": false}}}"#]);
+        assert!(!auth_manager.verify_session(&api_with(&transport)).unwrap());
+        assert!(auth_manager.active_profile().unwrap().unwrap().verified_at.is_none());
+
+        // Never verified and offline
+        let transport = MockTransport::new();
+        transport.push_error("connection refused");
+        assert!(!auth_manager.verify_session(&api_with(&transport)).unwrap());
+    }
+
+    #[test]
+    fn test_saving_keeps_profiles_encrypted_with_another_key() {
+        let (auth_manager, temp_dir) = create_test_auth_manager();
+        auth_manager.save_profile(Profile::new("personal", "LEETCODE_SESSION=personal")).unwrap();
+
+        // Same auth.json, different key: "personal" can't be decrypted
+        let other_key = AuthManager::new(temp_dir.path())
+            .with_key_path(temp_dir.path().join("other.key"));
+        assert!(other_key.load_profile("personal").is_err());
+        other_key.save_profile(Profile::new("work", "LEETCODE_SESSION=work")).unwrap();
+        assert_eq!(other_key.list_profiles().unwrap().len(), 1);
+
+        // The original key still reads the untouched profile
+        let personal = auth_manager.load_profile("personal").unwrap().unwrap();
+        assert_eq!(personal.session_cookie, "LEETCODE_SESSION=personal");
+        assert!(auth_manager.load_profile("work").is_err());
+
+        // Removing the active profile can fall back to an unreadable one
+        assert!(other_key.remove_profile("work").unwrap());
+        assert!(other_key.active_profile().is_err());
+        assert_eq!(auth_manager.active_profile().unwrap().unwrap().name, "personal");
+    }
+
+    #[test]
+    fn test_corrupt_store_is_not_overwritten() {
+        let (auth_manager, _temp_dir) = create_test_auth_manager();
+        fs::write(&auth_manager.config_path, "{ not json").unwrap();
+
+        assert!(auth_manager.save_session("LEETCODE_SESSION=abc").is_err());
+        assert!(auth_manager.save_profile(Profile::new("work", "LEETCODE_SESSION=abc")).is_err());
+        assert_eq!(fs::read_to_string(&auth_manager.config_path).unwrap(), "{ not json");
+    }
 }
@@ -1,87 +1,355 @@
-use zed_extension_api::{SlashCommandOutput, Worktree};
+use zed_extension_api::{Range, SlashCommandOutput, SlashCommandOutputSection, Worktree};
 use crate::templates::TemplateGenerator;
-use crate::auth::AuthManager;
-use crate::api::LeetCodeApi;
-use crate::models::{ProblemFilters, Difficulty};
+use crate::auth::{AuthManager, Profile, DEFAULT_PROFILE};
+use crate::api::{LeetCodeApi, PollOptions};
+use crate::error::LeetCodeError;
+use crate::file_manager::{Config, FileManager, UserConfig};
+use crate::models::{UserStatus, Problem, ProblemCatalog, ProblemFilters, DEFAULT_PAGE_SIZE, ProblemPage, Difficulty, Site, SolveStatus, SortField, SortOrder};
+use crate::resolver::resolve_title_slug;
+
+/// Stdout beyond these limits is truncated in test output
+const MAX_STDOUT_LINES: usize = 50;
+const MAX_STDOUT_CHARS: usize = 4000;
 
 /// Handle /leetcode-login command
 /// Authenticates user with LeetCode session cookie
 pub fn handle_login(args: Vec<String>) -> Result<SlashCommandOutput, String> {
-    if args.is_empty() {
-        return Err("Session cookie is required. Usage: /leetcode-login <session-cookie>".to_string());
+    if args.is_empty() || args[0].starts_with("--") {
+        return Err("Session cookie is required. Usage: /leetcode-login <session-cookie> [--csrf <csrftoken>] [--site com|cn] [--profile <name>]".to_string());
     }
     
     let session_cookie = &args[0];
     
-    // Use current directory as fallback for config
-    let config_dir = std::env::current_dir()
-        .map_err(|e| format!("Failed to get current directory: {}", e))?
-        .join(".leetcode");
+    let options = parse_arguments(&args[1..]);
+    let auth_manager = auth_manager()?;
+    
+    // Profile to save into, re-using the active one when omitted
+    let profile_name = match options.iter().find(|(key, _)| key == "profile") {
+        Some((_, Some(value))) => validate_profile_name(value)?,
+        Some((_, None)) => return Err("--profile requires a name".to_string()),
+        None => auth_manager.active_profile().ok().flatten()
+            .map(|profile| profile.name)
+            .unwrap_or_else(|| DEFAULT_PROFILE.to_string()),
+    };
+    let existing_profile = auth_manager.load_profile(&profile_name).ok().flatten();
+    
+    // Parse site option, keeping the profile's or workspace's site when omitted
+    let site = match options.iter().find(|(key, _)| key == "site") {
+        Some((_, Some(value))) => value.parse::<Site>()?,
+        Some((_, None)) => return Err("--site requires a value (com|cn)".to_string()),
+        None => existing_profile.and_then(|profile| profile.site).unwrap_or_else(workspace_site),
+    };
     
-    let auth_manager = AuthManager::new(&config_dir);
+    // CSRF token is needed for test/submit; it may also be part of a full cookie string
+    let csrf_token = match options.iter().find(|(key, _)| key == "csrf") {
+        Some((_, Some(value))) => Some(value.clone()),
+        Some((_, None)) => return Err("--csrf requires a value".to_string()),
+        None => None,
+    };
     
     // Verify session cookie with API
-    let api = LeetCodeApi::with_session(session_cookie.clone());
-    match api.verify_authentication(session_cookie) {
-        Ok(true) => {
+    let api = LeetCodeApi::with_session(session_cookie.clone()).with_site(site);
+    match api.fetch_user_status(session_cookie) {
+        Ok(user_status) if user_status.is_signed_in => {
             // Save session if valid
-            match auth_manager.save_session(session_cookie) {
-                Ok(_) => Ok(SlashCommandOutput {
-                    text: "Successfully logged in to LeetCode! Session saved securely.".to_string(),
-                    sections: vec![],
-                }),
-                Err(e) => Err(format!("Authentication successful but failed to save session: {}", e)),
-            }
+            let mut profile = Profile::new(&profile_name, session_cookie);
+            profile.csrf_token = csrf_token;
+            profile.site = Some(site);
+            profile.username = user_status.username;
+            profile.verified_at = Some(chrono::Utc::now().to_rfc3339());
+            let account = profile_account(&profile);
+            
+            auth_manager.save_profile(profile)
+                .map_err(|e| format!("Authentication successful but failed to save session: {}", e))?;
+            
+            Ok(SlashCommandOutput {
+                text: format!(
+                    "Successfully logged in {} (profile `{}`)! Session saved securely to {}.",
+                    account,
+                    profile_name,
+                    auth_manager.config_path().display()
+                ),
+                sections: vec![],
+            })
+        },
+        Ok(_) | Err(LeetCodeError::Unauthenticated) => {
+            Err("Invalid session cookie. Please check your session cookie from browser.".to_string())
         },
-        Ok(false) => Err("Invalid session cookie. Please check your session cookie from browser.".to_string()),
-        Err(e) => Err(format!("Failed to verify session: {}", e)),
+        Err(e) => Err(format!("Failed to verify session: {}", describe_api_error(&e))),
     }
 }
 
+/// Handle /leetcode-profile command
+/// Lists, switches between and removes saved accounts
+pub fn handle_profile(args: Vec<String>) -> Result<SlashCommandOutput, String> {
+    const USAGE: &str = "Usage: /leetcode-profile [list | use <name> | remove <name>]";
+    
+    let auth_manager = auth_manager()?;
+    let text = match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        [] | ["list"] => {
+            let profiles = auth_manager.list_profiles()
+                .map_err(|e| format!("Failed to load profiles: {}", e))?;
+            let active = auth_manager.active_profile().ok().flatten().map(|profile| profile.name);
+            format_profiles(&profiles, active.as_deref())
+        },
+        ["use", name] => {
+            auth_manager.use_profile(name).map_err(|e| e.to_string())?;
+            let profile = auth_manager.active_profile()
+                .map_err(|e| format!("Failed to load profile: {}", e))?
+                .ok_or_else(|| format!("No profile named '{}'", name))?;
+            format!("Switched to profile `{}` ({}).", name, profile_account(&profile))
+        },
+        ["remove", name] => logout_profile(&auth_manager, Some(name))?,
+        _ => return Err(USAGE.to_string()),
+    };
+    
+    Ok(SlashCommandOutput { text, sections: vec![] })
+}
+
+/// Handle /leetcode-logout command
+/// Forgets the active profile, a named one, or every saved profile with --all
+pub fn handle_logout(args: Vec<String>) -> Result<SlashCommandOutput, String> {
+    let auth_manager = auth_manager()?;
+    
+    let text = match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["--all"] => {
+            auth_manager.logout()
+                .map_err(|e| format!("Failed to remove saved sessions: {}", e))?;
+            "Logged out of all profiles. Saved sessions were removed.".to_string()
+        },
+        [] => logout_profile(&auth_manager, None)?,
+        [name] if !name.starts_with("--") => logout_profile(&auth_manager, Some(name))?,
+        _ => return Err("Usage: /leetcode-logout [<profile> | --all]".to_string()),
+    };
+    
+    Ok(SlashCommandOutput { text, sections: vec![] })
+}
+
+/// Remove one profile, the active one when `name` is omitted
+fn logout_profile(auth_manager: &AuthManager, name: Option<&str>) -> Result<String, String> {
+    let name = match name {
+        Some(name) => name.to_string(),
+        None => auth_manager.active_profile()
+            .map_err(|e| format!("Failed to load session: {}", e))?
+            .map(|profile| profile.name)
+            .ok_or("You are not logged in.")?,
+    };
+    let removed = auth_manager.remove_profile(&name)
+        .map_err(|e| format!("Failed to remove saved session: {}", e))?;
+    if !removed {
+        return Err(format!("No profile named '{}'", name));
+    }
+    
+    Ok(match auth_manager.active_profile().ok().flatten() {
+        Some(active) => format!("Logged out of profile `{}`. Now using profile `{}` ({}).", name, active.name, profile_account(&active)),
+        None => format!("Logged out of profile `{}`.", name),
+    })
+}
+
+/// Handle /leetcode-whoami command
+/// Shows the account behind the active profile
+pub fn handle_whoami(_args: Vec<String>) -> Result<SlashCommandOutput, String> {
+    let auth_manager = auth_manager()?;
+    let profile = auth_manager.active_profile()
+        .map_err(|e| format!("Failed to load session: {}", e))?
+        .ok_or("You are not logged in. Use /leetcode-login <session-cookie> to log in.")?;
+    let site = profile.site.unwrap_or_else(workspace_site);
+    
+    let api = LeetCodeApi::with_session(profile.session_cookie.clone()).with_site(site);
+    let user_status = api.fetch_user_status(&profile.session_cookie)
+        .map_err(|e| session_request_failed("Failed to fetch account details", &e))?;
+    if !user_status.is_signed_in {
+        return Err(format!(
+            "The session saved in profile `{}` has expired. Please login again using /leetcode-login <session-cookie>",
+            profile.name
+        ));
+    }
+    let _ = auth_manager.record_verification(&profile.name, user_status.username.clone());
+    
+    Ok(SlashCommandOutput {
+        text: format_whoami(&user_status, &profile.name, site),
+        sections: vec![],
+    })
+}
+
+/// Account summary for /leetcode-whoami
+fn format_whoami(user_status: &UserStatus, profile_name: &str, site: Site) -> String {
+    let mut output = format!("# 👤 {}\n\n", user_status.username.as_deref().unwrap_or("Unknown user"));
+    if let Some(real_name) = user_status.real_name.as_deref().filter(|name| !name.is_empty()) {
+        output.push_str(&format!("**Name:** {}\n", real_name));
+    }
+    output.push_str(&format!("**Site:** {}\n", site));
+    output.push_str(&format!("**Profile:** {}\n", profile_name));
+    if let Some(is_premium) = user_status.is_premium {
+        output.push_str(&format!("**Premium:** {}\n", if is_premium { "⭐ Yes" } else { "No" }));
+    }
+    if let Some(avatar) = user_status.avatar.as_deref().filter(|url| !url.is_empty()) {
+        output.push_str(&format!("**Avatar:** {}\n", avatar));
+    }
+    output
+}
+
+/// Profile names are used in commands and file keys, so keep them simple
+fn validate_profile_name(name: &str) -> Result<String, String> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(format!("Invalid profile name '{}'. Use letters, digits, '-' and '_'", name));
+    }
+    Ok(name.to_string())
+}
+
+/// "as alice on leetcode.com", or just "to leetcode.com" without a username
+fn profile_account(profile: &Profile) -> String {
+    let site = profile.site.unwrap_or_else(workspace_site);
+    match &profile.username {
+        Some(username) => format!("as {} on {}", username, site),
+        None => format!("to {}", site),
+    }
+}
+
+/// Markdown table of saved profiles, marking the active one
+fn format_profiles(profiles: &[Profile], active: Option<&str>) -> String {
+    if profiles.is_empty() {
+        return "No saved profiles. Use /leetcode-login <session-cookie> --profile <name> to add one.".to_string();
+    }
+    
+    let mut output = String::from("# LeetCode Profiles\n\n| | Profile | Username | Site |\n|---|---|---|---|\n");
+    for profile in profiles {
+        let marker = if active == Some(profile.name.as_str()) { "▶" } else { "" };
+        output.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            marker,
+            profile.name,
+            profile.username.as_deref().unwrap_or("-"),
+            profile.site.unwrap_or_else(workspace_site)
+        ));
+    }
+    output.push_str("\nSwitch with `/leetcode-profile use <name>`.");
+    output
+}
+
+/// Workspace .leetcode/config.json, if present and readable
+fn workspace_config() -> Option<Config> {
+    std::env::current_dir()
+        .ok()
+        .and_then(|dir| FileManager::read_config(&dir.join(".leetcode")).ok().flatten())
+}
+
+/// Site of the active profile, falling back to the workspace setting
+pub fn configured_site() -> Site {
+    auth_manager().ok()
+        .and_then(|auth_manager| auth_manager.active_profile().ok().flatten())
+        .and_then(|profile| profile.site)
+        .unwrap_or_else(workspace_site)
+}
+
+/// Site selected in .leetcode/config.json, defaulting to leetcode.com
+fn workspace_site() -> Site {
+    workspace_config()
+        .map(|config| config.site)
+        .unwrap_or_default()
+}
+
+/// Judge polling settings from .leetcode/config.json
+fn configured_poll_options() -> PollOptions {
+    workspace_config()
+        .map(|config| PollOptions::with_timeout(std::time::Duration::from_secs(config.judge_timeout_secs)))
+        .unwrap_or_default()
+}
+
+/// Credential store for the current workspace
+///
+/// Credentials live in the per-user config directory unless the per-user
+/// config.json maps this workspace to another `credentials_dirs` entry;
+/// sessions saved by older versions in the workspace's .leetcode directory
+/// are moved there on first use.
+fn auth_manager() -> Result<AuthManager, String> {
+    let workspace_root = std::env::current_dir()
+        .map_err(|e| format!("Failed to get current directory: {}", e))?;
+    let user_dir = AuthManager::default_config_dir()
+        .ok_or("No per-user config directory to store LeetCode credentials in; set XDG_CONFIG_HOME or HOME")?;
+    let user_config = UserConfig::read(&user_dir)
+        .map_err(|e| format!("Failed to read {}: {}", user_dir.join("config.json").display(), e))?
+        .unwrap_or_default();
+    let session_trust_secs = workspace_config().unwrap_or_default().session_trust_secs;
+    
+    let credentials_dir = user_config.credentials_dir_for(&workspace_root).unwrap_or(user_dir);
+    let auth_manager = AuthManager::new(&credentials_dir)
+        .allow_git_tracked(user_config.allow_credentials_in_git)
+        .with_verification_window(std::time::Duration::from_secs(session_trust_secs));
+    Ok(match legacy_credentials_dir(&workspace_root) {
+        Some(legacy_dir) => auth_manager.with_legacy_dir(&legacy_dir),
+        None => auth_manager,
+    })
+}
+
+/// Workspace directory older versions saved credentials in
+#[cfg(not(test))]
+fn legacy_credentials_dir(workspace_root: &std::path::Path) -> Option<std::path::PathBuf> {
+    Some(workspace_root.join(".leetcode"))
+}
+
+/// Unit tests run in a real checkout, so they must never migrate (and
+/// delete) the developer's own .leetcode/auth.json
+#[cfg(test)]
+fn legacy_credentials_dir(_workspace_root: &std::path::Path) -> Option<std::path::PathBuf> {
+    None
+}
+
 /// Helper function to check if user is authenticated
+///
+/// Only asks LeetCode when the last successful check is older than
+/// `session_trust_secs`; see `AuthManager::verify_session`.
 pub fn is_user_authenticated() -> bool {
-    let config_dir = match std::env::current_dir() {
-        Ok(dir) => dir.join(".leetcode"),
-        Err(_) => return false,
+    let Ok(auth_manager) = auth_manager() else {
+        return false;
     };
     
-    let auth_manager = AuthManager::new(&config_dir);
-    let api = LeetCodeApi::new();
+    let api = LeetCodeApi::new().with_site(configured_site());
     auth_manager.is_authenticated(&api)
 }
 
 /// Helper function to get current session cookie
 pub fn get_current_session() -> Option<String> {
-    let config_dir = match std::env::current_dir() {
-        Ok(dir) => dir.join(".leetcode"),
-        Err(_) => return None,
-    };
-    
-    let auth_manager = AuthManager::new(&config_dir);
-    auth_manager.get_session_cookie().ok().flatten()
+    auth_manager().ok()?.get_session_cookie().ok().flatten()
 }
 
 /// Handle /leetcode-list command  
 /// Lists LeetCode problems with optional filtering
 pub fn handle_list(args: Vec<String>) -> Result<SlashCommandOutput, String> {
-    // Check authentication first
+    // Parse filtering arguments
+    let filters = parse_list_filters(&args)?;
+    
+    // Check authentication first; the synced catalog still works without a session
     if !is_user_authenticated() {
+        if let Some(output) = offline_list(&filters, OfflineReason::NotSignedIn) {
+            return Ok(output);
+        }
         return Err("Please login first using /leetcode-login <session-cookie>".to_string());
     }
-
-    // Parse filtering arguments
-    let filters = parse_list_filters(&args)?;
     
     // Get authenticated API client
-    let session_cookie = get_current_session()
-        .ok_or("No valid session found. Please login again.")?;
-    let api = LeetCodeApi::with_session(session_cookie);
+    let api = session_api()?;
+    let site = api.site();
+    
+    // Sites without server-side sorting sort the synced catalog, which can't rank by frequency
+    if let Some(field) = filters.sort_by.filter(|_| !site.supports_list_sorting()) {
+        return sorted_catalog_list(&filters, field, site);
+    }
     
-    // Fetch problems from API
-    let problems = api.fetch_problems(&filters)
-        .map_err(|e| format!("Failed to fetch problems: {}", e))?;
+    // Fetch problems from API, falling back to local data when offline
+    let page = match api.fetch_problems(&filters) {
+        Ok(page) => page,
+        Err(e) => {
+            let offline_results = match e {
+                LeetCodeError::Network(_) => offline_list(&filters, OfflineReason::Unreachable),
+                _ => None,
+            };
+            return offline_results
+                .ok_or_else(|| session_request_failed("Failed to fetch problems", &e));
+        },
+    };
     
-    if problems.is_empty() {
+    if page.questions.is_empty() {
         return Ok(SlashCommandOutput {
             text: "No problems found with the given filters.".to_string(),
             sections: vec![],
@@ -89,7 +357,7 @@ pub fn handle_list(args: Vec<String>) -> Result<SlashCommandOutput, String> {
     }
     
     // Format output
-    let output = format_problems_list(&problems, &filters);
+    let output = format_problems_list(&page, &filters);
     
     Ok(SlashCommandOutput {
         text: output,
@@ -97,31 +365,126 @@ pub fn handle_list(args: Vec<String>) -> Result<SlashCommandOutput, String> {
     })
 }
 
+/// List problems sorted across the whole synced catalog
+///
+/// Used on sites whose API can't sort; sorting a single fetched page would
+/// only reorder that page.
+fn sorted_catalog_list(filters: &ProblemFilters, field: SortField, site: Site) -> Result<SlashCommandOutput, String> {
+    if field == SortField::Frequency {
+        return Err(format!("Sorting by frequency is not supported on {}", site));
+    }
+    let catalog = synced_catalog()
+        .ok_or(format!("Sorting on {} needs the synced catalog; run /leetcode-sync first", site))?;
+    if !ProblemCatalog::supports(filters) {
+        return Err(format!("--company and --list can't be combined with --sort on {}", site));
+    }
+    
+    let page = catalog.query(filters);
+    let text = if page.questions.is_empty() {
+        "No problems found with the given filters.".to_string()
+    } else {
+        format_problems_list(&page, filters)
+    };
+    Ok(SlashCommandOutput { text, sections: vec![] })
+}
+
+/// Why a command answered from local data instead of LeetCode
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum OfflineReason {
+    /// No saved session, or it could not be verified
+    NotSignedIn,
+    /// The request to LeetCode failed at the network level
+    Unreachable,
+}
+
+impl OfflineReason {
+    /// Banner text explaining where the results come from
+    fn message(self) -> &'static str {
+        match self {
+            OfflineReason::NotSignedIn => "You are not logged in or your session could not be verified",
+            OfflineReason::Unreachable => "LeetCode is unreachable",
+        }
+    }
+}
+
+/// List problems from local data when LeetCode can't be used, for `reason`
+///
+/// Uses the synced catalog when it can answer the filters, otherwise a title
+/// search over the cached index. Returns None when neither is available.
+fn offline_list(filters: &ProblemFilters, reason: OfflineReason) -> Option<SlashCommandOutput> {
+    if let Some(catalog) = synced_catalog().filter(|_| ProblemCatalog::supports(filters)) {
+        let page = catalog.query(filters);
+        let mut text = format!(
+            "⚠️ {}; showing the catalog synced at {}.\n\n",
+            reason.message(), catalog.synced_at
+        );
+        if page.questions.is_empty() {
+            text.push_str("No problems found with the given filters.");
+        } else {
+            text.push_str(&format_problems_list(&page, filters));
+        }
+        return Some(SlashCommandOutput { text, sections: vec![] });
+    }
+    
+    let keywords = filters.search_keywords.as_deref()?;
+    let workspace_root = std::env::current_dir().ok()?;
+    let index = FileManager::new_with_path(workspace_root).ok()?
+        .load_problem_index().ok()??;
+    
+    Some(SlashCommandOutput {
+        text: format_index_search(&index.search(keywords), keywords, reason),
+        sections: vec![],
+    })
+}
+
+/// Catalog from the last /leetcode-sync, if it was synced from the configured site
+fn synced_catalog() -> Option<ProblemCatalog> {
+    let workspace_root = std::env::current_dir().ok()?;
+    FileManager::new_with_path(workspace_root).ok()?
+        .load_catalog().ok()?
+        .filter(|catalog| catalog.site == configured_site())
+}
+
+/// Handle /leetcode-sync command
+/// Downloads the full problem catalog for offline listing, search and random picks
+pub fn handle_sync(_args: Vec<String>) -> Result<SlashCommandOutput, String> {
+    if !is_user_authenticated() {
+        return Err("Please login first using /leetcode-login <session-cookie>".to_string());
+    }
+    
+    let api = session_api()?;
+    let site = api.site();
+    
+    let problems = api.fetch_problem_catalog()
+        .map_err(|e| session_request_failed("Failed to sync problems", &e))?;
+    let catalog = ProblemCatalog::new(site, problems);
+    
+    let workspace_root = std::env::current_dir()
+        .map_err(|e| format!("Failed to get current directory: {}", e))?;
+    let file_manager = FileManager::new_with_path(workspace_root)
+        .map_err(|e| format!("Failed to prepare .leetcode directory: {}", e))?;
+    file_manager.save_catalog(&catalog)
+        .map_err(|e| format!("Failed to save problem catalog: {}", e))?;
+    file_manager.save_problem_index(&catalog.to_index())
+        .map_err(|e| format!("Failed to save problem index: {}", e))?;
+    
+    Ok(SlashCommandOutput {
+        text: format_sync_summary(&catalog),
+        sections: vec![],
+    })
+}
+
 /// Handle /leetcode-show command
 /// Shows problem details and creates solution template
 pub fn handle_show(args: Vec<String>) -> Result<SlashCommandOutput, String> {
-    if args.is_empty() {
+    // Leading non-flag arguments form the identifier so titles with spaces work
+    let flag_start = args.iter().position(|arg| arg.starts_with("--")).unwrap_or(args.len());
+    let problem_identifier = args[..flag_start].join(" ");
+    if problem_identifier.is_empty() {
         return Err("Problem ID or title is required. Usage: /leetcode-show <problem-id> [--language <lang>]".to_string());
     }
     
-    let problem_identifier = &args[0];
-    
-    // Parse language option
-    let parsed_args = parse_arguments(&args[1..]);
-    let language = parsed_args.iter()
-        .find(|(key, _)| key == "language")
-        .and_then(|(_, value)| value.as_ref())
-        .unwrap_or(&"rust".to_string())
-        .clone();
-
-    // Check if language is supported
-    if !TemplateGenerator::get_supported_languages().contains(&language.as_str()) {
-        return Err(format!(
-            "Unsupported language: {}. Supported languages: {}", 
-            language,
-            TemplateGenerator::get_supported_languages().join(", ")
-        ));
-    }
+    let language = parse_language_option(&args[flag_start..])?;
     
     // Check authentication first
     if !is_user_authenticated() {
@@ -129,46 +492,253 @@ pub fn handle_show(args: Vec<String>) -> Result<SlashCommandOutput, String> {
     }
     
     // Get authenticated API client
-    let session_cookie = get_current_session()
-        .ok_or("No valid session found. Please login again.")?;
-    let api = LeetCodeApi::with_session(session_cookie);
+    let api = session_api()?;
+    
+    let workspace_root = std::env::current_dir()
+        .map_err(|e| format!("Failed to get current directory: {}", e))?;
+    let file_manager = FileManager::new_with_path(workspace_root)
+        .map_err(|e| format!("Failed to prepare .leetcode directory: {}", e))?;
+    
+    // Map ID, title or URL to the title slug the API expects
+    let title_slug = resolve_title_slug(&problem_identifier, &api, &file_manager)
+        .map_err(|e| match e.downcast_ref::<LeetCodeError>() {
+            Some(api_error) => session_request_failed(&format!("Failed to resolve problem {}", problem_identifier), api_error),
+            None => format!("Failed to resolve problem {}: {}", problem_identifier, e),
+        })?;
     
     // Fetch problem details
-    let problem_detail = api.fetch_problem_detail(&problem_identifier)
-        .map_err(|e| format!("Failed to fetch problem details: {}", e))?;
+    let problem_detail = api.fetch_problem_detail(&title_slug)
+        .map_err(|e| session_request_failed("Failed to fetch problem details", &e))?;
     
     let problem = problem_detail.ok_or(
         format!("Problem not found: {}", problem_identifier)
     )?;
+    // Cached so offline random picks can still write the template
+    let _ = file_manager.save_problem_detail(&problem);
+    
+    let solution_path = write_solution_template(&file_manager, &problem, &language)?;
+    
+    // Format output with problem details and file path
+    let output = format_problem_details(&problem, &solution_path, &language);
+    
+    Ok(SlashCommandOutput {
+        text: output,
+        sections: vec![],
+    })
+}
+
+/// Handle /leetcode-daily command
+/// Shows today's challenge and creates its solution template
+pub fn handle_daily(args: Vec<String>) -> Result<SlashCommandOutput, String> {
+    let language = parse_language_option(&args)?;
+    
+    if !is_user_authenticated() {
+        return Err("Please login first using /leetcode-login <session-cookie>".to_string());
+    }
+    
+    let api = session_api()?;
+    
+    let challenge = api.fetch_daily_challenge()
+        .map_err(|e| session_request_failed("Failed to fetch daily challenge", &e))?;
+    
+    let problem = api.fetch_problem_detail(&challenge.question.title_slug)
+        .map_err(|e| session_request_failed("Failed to fetch problem details", &e))?
+        .ok_or(format!("Problem not found: {}", challenge.question.title_slug))?;
+    
+    let workspace_root = std::env::current_dir()
+        .map_err(|e| format!("Failed to get current directory: {}", e))?;
+    let file_manager = FileManager::new_with_path(workspace_root)
+        .map_err(|e| format!("Failed to prepare .leetcode directory: {}", e))?;
+    
+    let solution_path = write_solution_template(&file_manager, &problem, &language)?;
+    
+    let mut output = format_daily_header(&challenge);
+    output.push_str(&format_problem_details(&problem, &solution_path, &language));
+    
+    Ok(SlashCommandOutput {
+        text: output,
+        sections: vec![],
+    })
+}
+
+/// Handle /leetcode-random command
+/// Picks a random non-premium problem, unsolved unless --status says otherwise,
+/// and creates its solution template
+pub fn handle_random(args: Vec<String>) -> Result<SlashCommandOutput, String> {
+    let (language_args, filter_args) = split_language_option(&args);
+    let language = parse_language_option(&language_args)?;
+    let mut filters = parse_list_filters(&filter_args)?;
+    if filters.limit.is_some() || filters.skip.is_some() {
+        return Err("--limit, --page and --skip are not supported by /leetcode-random".to_string());
+    }
+    // Unsolved by default; --status overrides, e.g. to revisit solved problems
+    filters.status.get_or_insert_with(|| "NOT_STARTED".to_string());
+    filters.premium_only = Some(false);
+    
+    // The synced catalog still works without a session
+    if !is_user_authenticated() {
+        if let Some(output) = offline_random(&filters, &language, OfflineReason::NotSignedIn)? {
+            return Ok(output);
+        }
+        return Err("Please login first using /leetcode-login <session-cookie>".to_string());
+    }
+    
+    let api = session_api()?;
+    
+    // Pick from the synced catalog when LeetCode's random query is unreachable
+    let title_slug = match api.fetch_random_question(&filters) {
+        Ok(title_slug) => title_slug.ok_or_else(|| no_random_match(&filters))?,
+        Err(e) => {
+            if matches!(e, LeetCodeError::Network(_))
+                && let Some(output) = offline_random(&filters, &language, OfflineReason::Unreachable)?
+            {
+                return Ok(output);
+            }
+            return Err(session_request_failed("Failed to pick a random problem", &e));
+        },
+    };
+    
+    let problem = api.fetch_problem_detail(&title_slug)
+        .map_err(|e| session_request_failed("Failed to fetch problem details", &e))?
+        .ok_or(format!("Problem not found: {}", title_slug))?;
+    
+    let workspace_root = std::env::current_dir()
+        .map_err(|e| format!("Failed to get current directory: {}", e))?;
+    let file_manager = FileManager::new_with_path(workspace_root)
+        .map_err(|e| format!("Failed to prepare .leetcode directory: {}", e))?;
+    // Cached so later offline picks can still write the template
+    let _ = file_manager.save_problem_detail(&problem);
     
-    // Create solution file manually
-    let config_dir = std::env::current_dir()
-        .map_err(|e| format!("Failed to get current directory: {}", e))?
-        .join(".leetcode");
+    let solution_path = write_solution_template(&file_manager, &problem, &language)?;
     
-    let solutions_dir = config_dir.join("solutions");
+    let mut output = random_pick_header(&filters);
+    output.push_str(&format_problem_details(&problem, &solution_path, &language));
+    
+    Ok(SlashCommandOutput {
+        text: output,
+        sections: vec![],
+    })
+}
+
+/// Pick a random problem from the synced catalog without contacting LeetCode
+///
+/// Writes the solution template when the pick's details are cached, otherwise
+/// reports the pick from catalog data. Returns None without a usable catalog.
+fn offline_random(filters: &ProblemFilters, language: &str, reason: OfflineReason) -> Result<Option<SlashCommandOutput>, String> {
+    let Some(catalog) = synced_catalog().filter(|_| ProblemCatalog::supports(filters)) else {
+        return Ok(None);
+    };
+    let problem = pick_random(&catalog, filters)
+        .ok_or_else(|| no_random_match(filters))?;
+    
+    let mut text = format!(
+        "⚠️ {}; picked from the catalog synced at {}.\n\n",
+        reason.message(), catalog.synced_at
+    );
+    text.push_str(&random_pick_header(filters));
+    
+    let workspace_root = std::env::current_dir()
+        .map_err(|e| format!("Failed to get current directory: {}", e))?;
+    let file_manager = FileManager::new_with_path(workspace_root)
+        .map_err(|e| format!("Failed to prepare .leetcode directory: {}", e))?;
+    match file_manager.load_problem_detail(&problem.frontend_id).ok().flatten() {
+        Some(detail) => {
+            let solution_path = write_solution_template(&file_manager, &detail, language)?;
+            text.push_str(&format_problem_details(&detail, &solution_path, language));
+        },
+        None => text.push_str(&format_catalog_pick(&problem, catalog.site)),
+    }
+    
+    Ok(Some(SlashCommandOutput { text, sections: vec![] }))
+}
+
+/// Problems a random pick is drawn from, following the effective `--status`
+fn random_pool(filters: &ProblemFilters) -> String {
+    match filters.status.as_deref() {
+        None | Some("NOT_STARTED") => "unsolved problems".to_string(),
+        Some(status) => format!("{} problems", status_label(status)),
+    }
+}
+
+/// Banner shown above a random pick
+fn random_pick_header(filters: &ProblemFilters) -> String {
+    format!("🎲 **Random pick** from {}\n\n", random_pool(filters))
+}
+
+/// Error for a random pick with no candidates
+fn no_random_match(filters: &ProblemFilters) -> String {
+    format!("No {} match the given filters.", random_pool(filters))
+}
+
+/// Pick a random problem from the catalog matching `filters`
+fn pick_random(catalog: &ProblemCatalog, filters: &ProblemFilters) -> Option<Problem> {
+    let total = catalog.query(&ProblemFilters { limit: Some(0), ..filters.clone() }).total;
+    if total == 0 {
+        return None;
+    }
+    
+    let skip = (crate::crypto::random_u32() % total as u32) as i32;
+    
+    catalog.query(&ProblemFilters { skip: Some(skip), limit: Some(1), ..filters.clone() })
+        .questions
+        .pop()
+}
+
+/// Read `--language`, defaulting to Rust, and check a template exists for it
+fn parse_language_option(args: &[String]) -> Result<String, String> {
+    let parsed_args = parse_arguments(args);
+    let language = parsed_args.iter()
+        .find(|(key, _)| key == "language")
+        .and_then(|(_, value)| value.clone())
+        .unwrap_or_else(|| "rust".to_string());
+
+    if !TemplateGenerator::get_supported_languages().contains(&language.as_str()) {
+        return Err(format!(
+            "Unsupported language: {}. Supported languages: {}", 
+            language,
+            TemplateGenerator::get_supported_languages().join(", ")
+        ));
+    }
+
+    Ok(language)
+}
+
+/// Separate `--language <lang>` from the remaining arguments
+fn split_language_option(args: &[String]) -> (Vec<String>, Vec<String>) {
+    let mut language_args = Vec::new();
+    let mut rest = Vec::new();
+    let mut i = 0;
+    
+    while i < args.len() {
+        if args[i] == "--language" {
+            let end = (i + 2).min(args.len());
+            language_args.extend_from_slice(&args[i..end]);
+            i = end;
+        } else {
+            rest.push(args[i].clone());
+            i += 1;
+        }
+    }
+    
+    (language_args, rest)
+}
+
+/// Generate the solution template under `.leetcode/solutions` and return its path
+fn write_solution_template(file_manager: &FileManager, problem: &crate::models::ProblemDetail, language: &str) -> Result<std::path::PathBuf, String> {
+    let solutions_dir = file_manager.get_leetcode_dir().join("solutions");
     std::fs::create_dir_all(&solutions_dir)
         .map_err(|e| format!("Failed to create solutions directory: {}", e))?;
     
-    // Generate template file
-    let template_generator = TemplateGenerator;
-    let template_content = TemplateGenerator::generate_template(&problem, &language)
+    let template_content = TemplateGenerator::generate_template(problem, language)
         .ok_or("Failed to generate template for this language".to_string())?;
     
-    let filename = TemplateGenerator::generate_filename(&problem, &language);
+    let filename = TemplateGenerator::generate_filename(problem, language);
     let solution_path = solutions_dir.join(filename);
     
-    // Write template to file
     std::fs::write(&solution_path, &template_content)
         .map_err(|e| format!("Failed to write solution file: {}", e))?;
     
-    // Format output with problem details and file path
-    let output = format_problem_details(&problem, &solution_path, &language);
-    
-    Ok(SlashCommandOutput {
-        text: output,
-        sections: vec![],
-    })
+    Ok(solution_path)
 }
 
 /// Handle /leetcode-test command
@@ -179,13 +749,12 @@ pub fn handle_test(args: Vec<String>, worktree: Option<&Worktree>) -> Result<Sla
         return Err("Please login first using /leetcode-login <session-cookie>".to_string());
     }
 
-    // Parse optional file path from arguments
-    let file_path = if args.is_empty() {
+    // Parse optional file path and custom input from arguments
+    let options = parse_test_options(&args)?;
+    let file_path = match options.file_path {
+        Some(path) => path,
         // Get current file from worktree if available
-        get_current_file_path(worktree)?
-    } else {
-        // Use provided file path
-        args[0].clone()
+        None => get_current_file_path(worktree)?,
     };
 
     // Extract problem information from filename
@@ -194,28 +763,21 @@ pub fn handle_test(args: Vec<String>, worktree: Option<&Worktree>) -> Result<Sla
     // Read file content
     let code = read_file_content(&file_path)?;
 
-    // Get authenticated API client
-    let config_dir = std::env::current_dir()
-        .map_err(|e| format!("Failed to get current directory: {}", e))?
-        .join(".leetcode");
-    
-    let auth_manager = AuthManager::new(&config_dir);
-    let session_opt = auth_manager.load_session()
-        .map_err(|e| format!("Failed to load session: {}", e))?;
-    
-    let session = session_opt.ok_or("No valid session found. Please login first.".to_string())?;
-    let api = LeetCodeApi::with_session(session);
+    // Get authenticated API client with CSRF token for code execution
+    let api = code_execution_api()?;
+
+    // The REST judge needs the numeric question ID; input defaults to all example testcases
+    let problem = fetch_problem_for_execution(&api, &title_slug)?;
+    let data_input = match options.data_input {
+        Some(input) => input,
+        None => problem.example_testcases.clone()
+            .unwrap_or_else(|| problem.sample_test_case.clone()),
+    };
 
     // Run test
-    match api.run_test(&title_slug, &code, &lang) {
-        Ok(test_result) => {
-            let output = format_test_result(&test_result);
-            Ok(SlashCommandOutput {
-                text: output,
-                sections: vec![],
-            })
-        },
-        Err(e) => Err(format!("Test execution failed: {}", e)),
+    match api.run_test_rest(&problem, &code, &lang, &data_input) {
+        Ok(test_result) => Ok(build_test_output(&test_result)),
+        Err(e) => Err(session_request_failed("Test execution failed", &e)),
     }
 }
 
@@ -242,20 +804,14 @@ pub fn handle_submit(args: Vec<String>, worktree: Option<&Worktree>) -> Result<S
     // Read file content
     let code = read_file_content(&file_path)?;
 
-    // Get authenticated API client
-    let config_dir = std::env::current_dir()
-        .map_err(|e| format!("Failed to get current directory: {}", e))?
-        .join(".leetcode");
-    
-    let auth_manager = AuthManager::new(&config_dir);
-    let session_opt = auth_manager.load_session()
-        .map_err(|e| format!("Failed to load session: {}", e))?;
-    
-    let session = session_opt.ok_or("No valid session found. Please login first.".to_string())?;
-    let api = LeetCodeApi::with_session(session);
+    // Get authenticated API client with CSRF token for code execution
+    let api = code_execution_api()?;
+
+    // The REST judge needs the numeric question ID
+    let problem = fetch_problem_for_execution(&api, &title_slug)?;
 
     // Submit solution
-    match api.submit_solution(&title_slug, &code, &lang) {
+    match api.submit_solution_rest(&problem, &code, &lang) {
         Ok(submission_result) => {
             let output = format_submission_result(&submission_result);
             Ok(SlashCommandOutput {
@@ -263,7 +819,139 @@ pub fn handle_submit(args: Vec<String>, worktree: Option<&Worktree>) -> Result<S
                 sections: vec![],
             })
         },
-        Err(e) => Err(format!("Submission failed: {}", e)),
+        Err(e) => Err(session_request_failed("Submission failed", &e)),
+    }
+}
+
+/// Options accepted by /leetcode-test
+#[derive(Debug, Default, PartialEq)]
+struct TestOptions {
+    file_path: Option<String>,
+    data_input: Option<String>,
+}
+
+/// Parse `[file-path] [--input <testcase>] [--input-file <path>]`
+///
+/// `--input` takes every token up to the next flag; a literal `\n` separates
+/// arguments, mirroring the testcase box on the website.
+fn parse_test_options(args: &[String]) -> Result<TestOptions, String> {
+    let mut options = TestOptions::default();
+    let mut input_file = None;
+    let mut i = 0;
+    
+    while i < args.len() {
+        match args[i].as_str() {
+            "--input" => {
+                let values: Vec<&str> = args[i + 1..].iter()
+                    .take_while(|arg| !arg.starts_with("--"))
+                    .map(|arg| arg.as_str())
+                    .collect();
+                if values.is_empty() {
+                    return Err("--input requires a value, e.g. --input [2,7,11,15]\\n9".to_string());
+                }
+                options.data_input = Some(values.join(" ").replace("\\n", "\n"));
+                i += 1 + values.len();
+            },
+            "--input-file" => {
+                if i + 1 >= args.len() || args[i + 1].starts_with("--") {
+                    return Err("--input-file requires a path".to_string());
+                }
+                input_file = Some(args[i + 1].clone());
+                i += 2;
+            },
+            arg if arg.starts_with("--") => {
+                return Err(format!("Unknown option: {}. Available options: --input, --input-file", arg));
+            },
+            arg => {
+                if options.file_path.is_some() {
+                    return Err(format!("Unexpected argument: {}", arg));
+                }
+                options.file_path = Some(arg.to_string());
+                i += 1;
+            }
+        }
+    }
+    
+    if let Some(path) = input_file {
+        if options.data_input.is_some() {
+            return Err("Use either --input or --input-file, not both".to_string());
+        }
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read input file {}: {}", path, e))?;
+        options.data_input = Some(content.trim_end().to_string());
+    }
+    
+    Ok(options)
+}
+
+/// Fetch problem details needed by the REST interpret/submit endpoints
+fn fetch_problem_for_execution(api: &LeetCodeApi, title_slug: &str) -> Result<crate::models::ProblemDetail, String> {
+    api.fetch_problem_detail(title_slug)
+        .map_err(|e| session_request_failed("Failed to fetch problem details", &e))?
+        .ok_or_else(|| format!("Problem not found: {}", title_slug))
+}
+
+/// Build an API client from the active profile's session, site and CSRF token
+fn session_api() -> Result<LeetCodeApi, String> {
+    let profile = auth_manager()?.active_profile()
+        .map_err(|e| format!("Failed to load session: {}", e))?
+        .ok_or("No valid session found. Please login again.")?;
+    
+    let api = LeetCodeApi::with_session(profile.session_cookie)
+        .with_site(profile.site.unwrap_or_else(workspace_site));
+    Ok(match profile.csrf_token {
+        Some(token) => api.with_csrf_token(token),
+        None => api,
+    })
+}
+
+/// Session API client that waits for the judge as configured
+fn code_execution_api() -> Result<LeetCodeApi, String> {
+    Ok(session_api()?.with_poll_options(configured_poll_options()))
+}
+
+/// Report a failed request made with the saved session
+///
+/// When LeetCode rejected the session, its cached verification is dropped
+/// so the next command checks with LeetCode again.
+fn session_request_failed(context: &str, error: &LeetCodeError) -> String {
+    if *error == LeetCodeError::Unauthenticated
+        && let Ok(auth_manager) = auth_manager()
+    {
+        let _ = auth_manager.forget_verification();
+    }
+    format!("{}: {}", context, describe_api_error(error))
+}
+
+/// Turn an API error into an actionable message for the user
+fn describe_api_error(error: &LeetCodeError) -> String {
+    match error {
+        LeetCodeError::Unauthenticated => {
+            "Your LeetCode session is missing or expired. Please login again using /leetcode-login <session-cookie>".to_string()
+        },
+        LeetCodeError::CsrfMismatch => {
+            "LeetCode rejected the CSRF token. Please login again with /leetcode-login <session-cookie> --csrf <csrftoken>".to_string()
+        },
+        LeetCodeError::RateLimited { retry_after: Some(delay) } => {
+            format!("LeetCode is rate limiting requests. Please wait {} seconds and try again.", delay.as_secs())
+        },
+        LeetCodeError::RateLimited { retry_after: None } => {
+            "LeetCode is rate limiting requests. Please wait a minute and try again.".to_string()
+        },
+        LeetCodeError::PremiumRequired => {
+            "This problem requires a LeetCode Premium subscription.".to_string()
+        },
+        LeetCodeError::NotFound(what) => {
+            format!("Not found on LeetCode ({}). Check the problem ID, title or slug.", what)
+        },
+        LeetCodeError::Network(message) => {
+            format!("Could not reach LeetCode ({}). Check your network connection and try again.", message)
+        },
+        LeetCodeError::Judge(message) => message.clone(),
+        LeetCodeError::Parse(message) => {
+            format!("Unexpected response from LeetCode ({}). The API may have changed.", message)
+        },
+        LeetCodeError::Api(message) => format!("LeetCode returned an error: {}", message),
     }
 }
 
@@ -274,8 +962,8 @@ pub fn parse_arguments(args: &[String]) -> Vec<(String, Option<String>)> {
     
     while i < args.len() {
         let arg = &args[i];
-        if arg.starts_with("--") {
-            let key = arg[2..].to_string();
+        if let Some(key) = arg.strip_prefix("--") {
+            let key = key.to_string();
             let value = if i + 1 < args.len() && !args[i + 1].starts_with("--") {
                 i += 1;
                 Some(args[i].clone())
@@ -316,8 +1004,10 @@ mod tests {
     #[test]
     fn test_helper_functions() {
         // Test authentication helper functions
-        // These will return false/None in test environment but shouldn't panic
-        assert_eq!(is_user_authenticated(), false);
+        // These will return false/None in test environment but shouldn't panic;
+        // unit tests never see the developer's real ~/.config
+        assert!(AuthManager::default_config_dir().unwrap().starts_with(std::env::temp_dir()));
+        assert!(!is_user_authenticated());
         assert_eq!(get_current_session(), None);
     }
 
@@ -341,6 +1031,24 @@ mod tests {
         assert!(result.unwrap_err().contains("Session cookie is required"));
     }
 
+    #[test]
+    fn test_handle_login_invalid_site() {
+        let args = vec!["session_cookie_123".to_string(), "--site".to_string(), "jp".to_string()];
+        let result = handle_login(args);
+        
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("Invalid site: jp"));
+    }
+
+    #[test]
+    fn test_handle_login_csrf_requires_value() {
+        let args = vec!["session_cookie_123".to_string(), "--csrf".to_string()];
+        let result = handle_login(args);
+        
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("--csrf requires a value"));
+    }
+
     #[test]
     fn test_handle_list_no_filters() {
         let args = vec![];
@@ -383,24 +1091,77 @@ mod tests {
     }
 
     #[test]
-    fn test_handle_show_with_language() {
-        let args = vec!["1".to_string(), "--language".to_string(), "python".to_string()];
-        let result = handle_show(args);
-        
-        // Should fail because not authenticated
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("Please login first"));
+    fn test_handle_show_with_language() {
+        let args = vec!["1".to_string(), "--language".to_string(), "python".to_string()];
+        let result = handle_show(args);
+        
+        // Should fail because not authenticated
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("Please login first"));
+    }
+
+    #[test]
+    fn test_handle_show_unsupported_language() {
+        let args = vec!["1".to_string(), "--language".to_string(), "cobol".to_string()];
+        let result = handle_show(args);
+        
+        assert!(result.is_err());
+        let error = result.unwrap_err();
+        assert!(error.contains("Unsupported language: cobol"));
+        assert!(error.contains("Supported languages:"));
+    }
+
+    #[test]
+    fn test_handle_daily_no_auth() {
+        let result = handle_daily(vec![]);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("Please login first"));
+    }
+
+    #[test]
+    fn test_handle_daily_unsupported_language() {
+        let args = vec!["--language".to_string(), "cobol".to_string()];
+        let result = handle_daily(args);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("Unsupported language: cobol"));
+    }
+
+    #[test]
+    fn test_handle_random_no_auth() {
+        let args = vec!["--difficulty".to_string(), "easy".to_string(), "--language".to_string(), "python".to_string()];
+        let result = handle_random(args);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("Please login first"));
+    }
+
+    #[test]
+    fn test_handle_random_rejects_invalid_filters() {
+        let result = handle_random(vec!["--difficulty".to_string(), "extreme".to_string()]);
+        assert!(result.unwrap_err().contains("Invalid difficulty"));
+
+        let result = handle_random(vec!["--limit".to_string(), "5".to_string()]);
+        assert!(result.unwrap_err().contains("are not supported by /leetcode-random"));
+    }
+
+    #[test]
+    fn test_split_language_option() {
+        let args: Vec<String> = ["--tag", "array", "--language", "cpp", "--difficulty", "hard"]
+            .iter().map(|s| s.to_string()).collect();
+        let (language_args, rest) = split_language_option(&args);
+
+        assert_eq!(language_args, vec!["--language", "cpp"]);
+        assert_eq!(rest, vec!["--tag", "array", "--difficulty", "hard"]);
     }
 
     #[test]
-    fn test_handle_show_unsupported_language() {
-        let args = vec!["1".to_string(), "--language".to_string(), "cobol".to_string()];
-        let result = handle_show(args);
-        
+    fn test_handle_sync_no_auth() {
+        let result = handle_sync(vec![]);
+
         assert!(result.is_err());
-        let error = result.unwrap_err();
-        assert!(error.contains("Unsupported language: cobol"));
-        assert!(error.contains("Supported languages:"));
+        assert!(result.unwrap_err().contains("Please login first"));
     }
 
     #[test]
@@ -421,6 +1182,16 @@ mod tests {
         assert!(result.unwrap_err().contains("Please login first"));
     }
 
+    #[test]
+    fn test_describe_api_error() {
+        assert!(describe_api_error(&LeetCodeError::Unauthenticated).contains("/leetcode-login"));
+        assert!(describe_api_error(&LeetCodeError::CsrfMismatch).contains("--csrf"));
+        let rate_limited = LeetCodeError::RateLimited { retry_after: Some(std::time::Duration::from_secs(12)) };
+        assert!(describe_api_error(&rate_limited).contains("wait 12 seconds"));
+        assert!(describe_api_error(&LeetCodeError::PremiumRequired).contains("Premium"));
+        assert!(describe_api_error(&LeetCodeError::Network("Could not resolve host".to_string())).contains("network connection"));
+    }
+
     #[test]
     fn test_parse_arguments_flags() {
         let args = vec!["--difficulty".to_string(), "easy".to_string(), "--tag".to_string(), "array".to_string()];
@@ -445,6 +1216,8 @@ mod tests {
 /// Parse command line arguments for list filtering
 fn parse_list_filters(args: &[String]) -> Result<ProblemFilters, String> {
     let mut filters = ProblemFilters::default();
+    let mut page: Option<i32> = None;
+    let mut order: Option<SortOrder> = None;
     let mut i = 0;
     
     while i < args.len() {
@@ -477,17 +1250,74 @@ fn parse_list_filters(args: &[String]) -> Result<ProblemFilters, String> {
                 filters.company_tag = Some(args[i + 1].clone());
                 i += 2;
             },
+            "--search" => {
+                // Keywords run until the next option so multi-word titles work unquoted
+                let end = args[i + 1..].iter()
+                    .position(|arg| arg.starts_with("--"))
+                    .map_or(args.len(), |offset| i + 1 + offset);
+                if end == i + 1 {
+                    return Err("--search requires keywords".to_string());
+                }
+                filters.search_keywords = Some(args[i + 1..end].join(" "));
+                i = end;
+            },
+            "--sort" => {
+                if i + 1 >= args.len() {
+                    return Err("--sort requires a value (id|acceptance|difficulty|frequency)".to_string());
+                }
+                filters.sort_by = Some(args[i + 1].parse()?);
+                i += 2;
+            },
+            "--order" => {
+                if i + 1 >= args.len() {
+                    return Err("--order requires a value (asc|desc)".to_string());
+                }
+                order = Some(args[i + 1].parse()?);
+                i += 2;
+            },
+            "--status" => {
+                if i + 1 >= args.len() {
+                    return Err("--status requires a value (todo|solved|attempted)".to_string());
+                }
+                filters.status = Some(parse_status(&args[i + 1])?.to_string());
+                i += 2;
+            },
+            "--list" => {
+                if i + 1 >= args.len() {
+                    return Err("--list requires a favorite list ID".to_string());
+                }
+                filters.list_id = Some(args[i + 1].clone());
+                i += 2;
+            },
+            "--page" => {
+                if i + 1 >= args.len() {
+                    return Err("--page requires a number".to_string());
+                }
+                let page_str = &args[i + 1];
+                page = Some(page_str.parse().ok().filter(|page: &i32| *page >= 1)
+                    .ok_or(format!("Invalid page: {}. Must be a number starting at 1", page_str))?);
+                i += 2;
+            },
+            "--skip" => {
+                if i + 1 >= args.len() {
+                    return Err("--skip requires a number".to_string());
+                }
+                let skip_str = &args[i + 1];
+                filters.skip = Some(skip_str.parse().ok().filter(|skip: &i32| *skip >= 0)
+                    .ok_or(format!("Invalid skip: {}. Must be a non-negative number", skip_str))?);
+                i += 2;
+            },
             "--limit" => {
                 if i + 1 >= args.len() {
                     return Err("--limit requires a number".to_string());
                 }
                 let limit_str = &args[i + 1];
-                filters.limit = Some(limit_str.parse().map_err(|_| 
-                    format!("Invalid limit: {}. Must be a number", limit_str))?);
+                filters.limit = Some(limit_str.parse().ok().filter(|limit: &i32| *limit >= 1)
+                    .ok_or(format!("Invalid limit: {}. Must be a number starting at 1", limit_str))?);
                 i += 2;
             },
             arg if arg.starts_with("--") => {
-                return Err(format!("Unknown option: {}. Available options: --difficulty, --tag, --company, --limit", arg));
+                return Err(format!("Unknown option: {}. Available options: --difficulty, --tag, --company, --search, --status, --list, --sort, --order, --page, --skip, --limit", arg));
             },
             _ => {
                 return Err(format!("Unexpected argument: {}. Use --option value format", &args[i]));
@@ -495,19 +1325,61 @@ fn parse_list_filters(args: &[String]) -> Result<ProblemFilters, String> {
         }
     }
     
+    if let Some(order) = order {
+        if filters.sort_by.is_none() {
+            return Err("--order requires --sort".to_string());
+        }
+        filters.sort_order = order;
+    }
+    
+    // Pages are resolved last so --page works before or after --limit
+    if let Some(page) = page {
+        if filters.skip.is_some() {
+            return Err("Use either --page or --skip, not both".to_string());
+        }
+        let skip = (page - 1).checked_mul(filters.limit.unwrap_or(DEFAULT_PAGE_SIZE))
+            .ok_or(format!("Invalid page: {}. Too large for the page size", page))?;
+        filters.skip = Some(skip);
+    }
+    
     Ok(filters)
 }
 
+/// Map a `--status` value to the status filter LeetCode expects
+fn parse_status(value: &str) -> Result<&'static str, String> {
+    match value.to_lowercase().as_str() {
+        "todo" => Ok("NOT_STARTED"),
+        "solved" => Ok("AC"),
+        "attempted" => Ok("TRIED"),
+        _ => Err(format!("Invalid status: {}. Use todo|solved|attempted", value)),
+    }
+}
+
+/// `--status` value for a LeetCode status filter, for display and next-page commands
+fn status_label(status: &str) -> &str {
+    match status {
+        "NOT_STARTED" => "todo",
+        "AC" => "solved",
+        "TRIED" => "attempted",
+        other => other,
+    }
+}
+
 /// Format problems list for display
-fn format_problems_list(problems: &[crate::models::Problem], filters: &ProblemFilters) -> String {
+fn format_problems_list(page: &ProblemPage, filters: &ProblemFilters) -> String {
     let mut output = String::new();
     
     // Header with filter info
     output.push_str("# LeetCode Problems\n\n");
     
     // Filter summary
-    if filters.difficulty.is_some() || !filters.tags.is_empty() || filters.company_tag.is_some() {
+    if filters.difficulty.is_some() || !filters.tags.is_empty() || filters.company_tag.is_some()
+        || filters.search_keywords.is_some() || filters.status.is_some() || filters.list_id.is_some()
+        || filters.sort_by.is_some() {
         output.push_str("**Filters applied:**\n");
+        if let Some(ref keywords) = filters.search_keywords {
+            output.push_str(&format!("- Search: {}\n", keywords));
+        }
         if let Some(ref difficulty) = filters.difficulty {
             output.push_str(&format!("- Difficulty: {}\n", difficulty));
         }
@@ -517,14 +1389,30 @@ fn format_problems_list(problems: &[crate::models::Problem], filters: &ProblemFi
         if let Some(ref company) = filters.company_tag {
             output.push_str(&format!("- Company: {}\n", company));
         }
-        output.push_str("\n");
+        if let Some(ref status) = filters.status {
+            output.push_str(&format!("- Status: {}\n", status_label(status)));
+        }
+        if let Some(ref list_id) = filters.list_id {
+            output.push_str(&format!("- List: {}\n", list_id));
+        }
+        if let Some(sort_by) = filters.sort_by {
+            output.push_str(&format!("- Sort: {} ({})\n", sort_by, filters.sort_order));
+        }
+        output.push('\n');
     }
     
     // Problems table
-    output.push_str("| # | Title | Difficulty | Acceptance | Tags |\n");
-    output.push_str("|---|-------|------------|------------|------|\n");
-    
-    for problem in problems.iter().take(filters.limit.unwrap_or(50) as usize) {
+    output.push_str("| | # | Title | Difficulty | Acceptance | Tags |\n");
+    output.push_str("|-|---|-------|------------|------------|------|\n");
+    
+    let limit = usize::try_from(page.limit).unwrap_or(0);
+    for problem in page.questions.iter().take(limit) {
+        let status_marker = match problem.solve_status() {
+            SolveStatus::Solved => "✅",
+            SolveStatus::Attempted => "🟠",
+            SolveStatus::NotStarted => "",
+        };
+        
         let difficulty_emoji = match problem.difficulty {
             Difficulty::Easy => "🟢",
             Difficulty::Medium => "🟡", 
@@ -538,9 +1426,10 @@ fn format_problems_list(problems: &[crate::models::Problem], filters: &ProblemFi
             .join(", ");
         
         output.push_str(&format!(
-            "| {} | {} | {} {} | {:.1}% | {} |\n",
+            "| {} | {} | {} | {} {} | {:.1}% | {} |\n",
+            status_marker,
             problem.frontend_id,
-            problem.title,
+            problem.translated_title.as_deref().unwrap_or(&problem.title),
             difficulty_emoji,
             problem.difficulty,
             problem.acceptance_rate * 100.0,
@@ -548,11 +1437,141 @@ fn format_problems_list(problems: &[crate::models::Problem], filters: &ProblemFi
         ));
     }
     
-    output.push_str(&format!("\n**Showing {} problems**", problems.len().min(filters.limit.unwrap_or(50) as usize)));
-    if problems.len() > filters.limit.unwrap_or(50) as usize {
-        output.push_str(&format!(" (out of {} total)", problems.len()));
+    let shown = &page.questions[..page.questions.len().min(limit)];
+    let solved = shown.iter().filter(|problem| problem.solve_status() == SolveStatus::Solved).count();
+    output.push_str(&format!(
+        "\n**Page {} of {}** · showing {}–{} of {} problems · ✅ {}/{} solved on this page",
+        page.page_number(),
+        page.page_count(),
+        page.skip + 1,
+        page.skip + shown.len() as i32,
+        page.total,
+        solved,
+        shown.len()
+    ));
+    if let Some(skip) = page.previous_skip() {
+        output.push_str(&format!("\n\nPrevious page: `{}`", list_command(filters, skip, page.limit)));
+    }
+    if let Some(skip) = page.next_skip() {
+        output.push_str(&format!("\n\nNext page: `{}`", list_command(filters, skip, page.limit)));
+    }
+    
+    output
+}
+
+/// Rebuild the /leetcode-list command for `filters` starting at `skip`
+///
+/// Uses `--page` when `skip` falls on a page boundary and `--skip` otherwise.
+fn list_command(filters: &ProblemFilters, skip: i32, limit: i32) -> String {
+    let mut command = String::from("/leetcode-list");
+    
+    if let Some(ref keywords) = filters.search_keywords {
+        command.push_str(&format!(" --search {}", keywords));
+    }
+    if let Some(ref difficulty) = filters.difficulty {
+        command.push_str(&format!(" --difficulty {}", difficulty.to_string().to_lowercase()));
+    }
+    if !filters.tags.is_empty() {
+        command.push_str(&format!(" --tag {}", filters.tags.join(",")));
+    }
+    if let Some(ref company) = filters.company_tag {
+        command.push_str(&format!(" --company {}", company));
+    }
+    if let Some(ref status) = filters.status {
+        command.push_str(&format!(" --status {}", status_label(status)));
+    }
+    if let Some(ref list_id) = filters.list_id {
+        command.push_str(&format!(" --list {}", list_id));
+    }
+    if let Some(sort_by) = filters.sort_by {
+        command.push_str(&format!(" --sort {} --order {}", sort_by, filters.sort_order));
+    }
+    if let Some(limit) = filters.limit {
+        command.push_str(&format!(" --limit {}", limit));
+    }
+    if skip % limit.max(1) == 0 {
+        command.push_str(&format!(" --page {}", skip / limit.max(1) + 1));
+    } else {
+        command.push_str(&format!(" --skip {}", skip));
+    }
+    
+    command
+}
+
+/// Format the date and completion line shown above the daily challenge
+fn format_daily_header(challenge: &crate::models::DailyChallenge) -> String {
+    let status = if challenge.is_completed() {
+        "✅ Completed"
+    } else {
+        "⬜ Not completed yet"
+    };
+    
+    format!("📅 **Daily Challenge:** {} · {}\n\n", challenge.date, status)
+}
+
+/// Summarize a freshly synced catalog
+fn format_sync_summary(catalog: &ProblemCatalog) -> String {
+    let count = |status: SolveStatus| catalog.problems.iter().filter(|problem| problem.solve_status() == status).count();
+    let premium = catalog.problems.iter().filter(|problem| problem.is_paid_only).count();
+    
+    format!(
+        "✅ Synced {} problems from {}\n\n- Solved: {}\n- Attempted: {}\n- Premium: {}\n\n\
+         Saved to `.leetcode/catalog.json` at {}. `/leetcode-list` and `/leetcode-random` \
+         fall back to it when LeetCode is unreachable; run `/leetcode-sync` again to refresh it.",
+        catalog.problems.len(),
+        catalog.site,
+        count(SolveStatus::Solved),
+        count(SolveStatus::Attempted),
+        premium,
+        catalog.synced_at
+    )
+}
+
+/// Format offline search results from the cached problem index
+fn format_index_search(entries: &[&crate::models::ProblemIndexEntry], keywords: &str, reason: OfflineReason) -> String {
+    let mut output = String::from("# LeetCode Problems\n\n");
+    output.push_str(&format!("⚠️ {}; results come from the cached problem index.\n\n", reason.message()));
+    
+    if entries.is_empty() {
+        output.push_str(&format!("No cached problems match \"{}\".", keywords));
+        return output;
+    }
+    
+    output.push_str("| # | Title | Slug |\n");
+    output.push_str("|---|-------|------|\n");
+    for entry in entries.iter().take(50) {
+        output.push_str(&format!("| {} | {} | `{}` |\n", entry.frontend_id, entry.title, entry.title_slug));
     }
     
+    output.push_str(&format!("\n**Showing {} of {} matches for \"{}\"**", entries.len().min(50), entries.len(), keywords));
+    output
+}
+
+/// Format a catalog entry picked while the problem details are not cached
+fn format_catalog_pick(problem: &Problem, site: Site) -> String {
+    let difficulty_emoji = match problem.difficulty {
+        Difficulty::Easy => "🟢",
+        Difficulty::Medium => "🟡",
+        Difficulty::Hard => "🔴",
+    };
+    
+    let mut output = match problem.translated_title {
+        Some(ref translated) => format!("# {}. {} ({})\n\n", problem.frontend_id, translated, problem.title),
+        None => format!("# {}. {}\n\n", problem.frontend_id, problem.title),
+    };
+    output.push_str(&format!("**Difficulty:** {} {}\n", difficulty_emoji, problem.difficulty));
+    if !problem.tags.is_empty() {
+        let tags = problem.tags.iter()
+            .map(|tag| format!("`{}`", tag.name))
+            .collect::<Vec<_>>()
+            .join(" ");
+        output.push_str(&format!("**Tags:** {}\n", tags));
+    }
+    output.push_str(&format!("**Link:** {}/problems/{}/\n\n", site.base_url(), problem.title_slug));
+    output.push_str(&format!(
+        "The problem details are not cached, so no solution file was created. Run `/leetcode-show {}` once LeetCode is reachable.",
+        problem.title_slug
+    ));
     output
 }
 
@@ -561,7 +1580,10 @@ fn format_problem_details(problem: &crate::models::ProblemDetail, solution_path:
     let mut output = String::new();
     
     // Problem header
-    output.push_str(&format!("# {}. {}\n\n", problem.frontend_id, problem.title));
+    match problem.translated_title {
+        Some(ref translated) => output.push_str(&format!("# {}. {} ({})\n\n", problem.frontend_id, translated, problem.title)),
+        None => output.push_str(&format!("# {}. {}\n\n", problem.frontend_id, problem.title)),
+    }
     
     // Difficulty and stats
     let difficulty_emoji = match problem.difficulty {
@@ -583,11 +1605,12 @@ fn format_problem_details(problem: &crate::models::ProblemDetail, solution_path:
         output.push_str(&format!("**Tags:** {}\n", tags));
     }
     
-    output.push_str("\n");
+    output.push('\n');
     
     // Problem description (simplified HTML removal)
     output.push_str("## Problem Description\n\n");
-    let cleaned_content = simple_html_to_text(&problem.content);
+    let content = problem.translated_content.as_deref().unwrap_or(&problem.content);
+    let cleaned_content = simple_html_to_text(content);
     output.push_str(&cleaned_content);
     output.push_str("\n\n");
     
@@ -738,7 +1761,10 @@ fn format_test_result(result: &crate::models::TestResult) -> String {
         output.push_str("\n```\n");
     }
     
-    if let Some(ref failed_case) = result.failed_test_case {
+    // Per-testcase breakdown, falling back to the single failure summary
+    if !result.cases.is_empty() {
+        output.push_str(&format_test_cases(&result.cases));
+    } else if let Some(ref failed_case) = result.failed_test_case {
         output.push_str("\n## Failed Test Case\n\n");
         output.push_str("```\n");
         output.push_str(failed_case);
@@ -754,6 +1780,122 @@ fn format_test_result(result: &crate::models::TestResult) -> String {
     output
 }
 
+/// Build /leetcode-test output with stdout in a collapsible section
+fn build_test_output(result: &crate::models::TestResult) -> SlashCommandOutput {
+    let mut text = format_test_result(result);
+    let mut sections = vec![];
+    
+    if let Some(stdout) = format_stdout(result) {
+        let start = text.len() as u32;
+        text.push_str(&stdout);
+        sections.push(SlashCommandOutputSection {
+            range: Range { start, end: text.len() as u32 },
+            label: "Stdout".to_string(),
+        });
+    }
+    
+    SlashCommandOutput { text, sections }
+}
+
+/// Format stdout per testcase, or the combined stdout when there is no breakdown
+fn format_stdout(result: &crate::models::TestResult) -> Option<String> {
+    let blocks: Vec<(String, &str)> = if result.cases.iter().any(|case| case.stdout.is_some()) {
+        result.cases.iter()
+            .enumerate()
+            .filter_map(|(i, case)| case.stdout.as_deref().map(|out| (format!("Case {}", i + 1), out)))
+            .collect()
+    } else {
+        result.stdout.as_deref()
+            .map(|out| vec![("All cases".to_string(), out)])
+            .unwrap_or_default()
+    };
+    
+    if blocks.is_empty() {
+        return None;
+    }
+    
+    let mut output = String::from("\n## Stdout\n");
+    for (label, stdout) in blocks {
+        output.push_str(&format!("\n**{}**\n\n", label));
+        output.push_str("```\n");
+        output.push_str(&truncate_output(stdout.trim_end()));
+        output.push_str("\n```\n");
+    }
+    
+    Some(output)
+}
+
+/// Cap output at `MAX_STDOUT_LINES` lines and `MAX_STDOUT_CHARS` characters
+fn truncate_output(output: &str) -> String {
+    let total_lines = output.lines().count();
+    let mut is_truncated = total_lines > MAX_STDOUT_LINES;
+    let mut truncated: String = output.lines()
+        .take(MAX_STDOUT_LINES)
+        .collect::<Vec<_>>()
+        .join("\n");
+    
+    if let Some((index, _)) = truncated.char_indices().nth(MAX_STDOUT_CHARS) {
+        truncated.truncate(index);
+        is_truncated = true;
+    }
+    
+    if is_truncated {
+        let shown_lines = truncated.lines().count();
+        truncated.push_str(&format!(
+            "\n... truncated ({} of {} lines shown)",
+            shown_lines, total_lines
+        ));
+    }
+    
+    truncated
+}
+
+/// Format a table of testcase outcomes followed by a diff for each mismatch
+fn format_test_cases(cases: &[crate::models::CaseOutcome]) -> String {
+    let mut output = String::new();
+    
+    output.push_str("\n## Test Cases\n\n");
+    output.push_str("| # | Result | Input | Expected | Actual |\n");
+    output.push_str("|---|--------|-------|----------|--------|\n");
+    
+    for (i, case) in cases.iter().enumerate() {
+        output.push_str(&format!(
+            "| {} | {} | {} | {} | {} |\n",
+            i + 1,
+            if case.passed { "✅" } else { "❌" },
+            format_table_cell(&case.input),
+            format_table_cell(&case.expected),
+            format_table_cell(&case.actual)
+        ));
+    }
+    
+    for (i, case) in cases.iter().enumerate().filter(|(_, case)| !case.passed) {
+        output.push_str(&format!("\n### Case {} Diff\n\n", i + 1));
+        output.push_str("```diff\n");
+        for line in case.expected.lines() {
+            output.push_str(&format!("- {}\n", line));
+        }
+        for line in case.actual.lines() {
+            output.push_str(&format!("+ {}\n", line));
+        }
+        output.push_str("```\n");
+    }
+    
+    output
+}
+
+/// Render a multi-line value as inline code spans safe for a markdown table cell
+fn format_table_cell(value: &str) -> String {
+    if value.trim().is_empty() {
+        return "-".to_string();
+    }
+    
+    value.lines()
+        .map(|line| format!("`{}`", line.replace('|', "\\|")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 /// Format submission result for display
 fn format_submission_result(result: &crate::models::SubmissionResult) -> String {
     let mut output = String::new();
@@ -763,10 +1905,10 @@ fn format_submission_result(result: &crate::models::SubmissionResult) -> String
     output.push_str(&format!("**Status:** {}\n", result.status));
     
     // Statistics
-    if let Some(total_correct) = result.total_correct {
-        if let Some(total_tests) = result.total_testcases {
-            output.push_str(&format!("**Test Cases Passed:** {}/{}\n", total_correct, total_tests));
-        }
+    if let Some(total_correct) = result.total_correct
+        && let Some(total_tests) = result.total_testcases
+    {
+        output.push_str(&format!("**Test Cases Passed:** {}/{}\n", total_correct, total_tests));
     }
     
     // Performance metrics
@@ -775,7 +1917,7 @@ fn format_submission_result(result: &crate::models::SubmissionResult) -> String
         if let Some(percentile) = result.runtime_percentile {
             output.push_str(&format!(" (beats {:.1}% of submissions)", percentile));
         }
-        output.push_str("\n");
+        output.push('\n');
     }
     
     if let Some(memory) = result.memory {
@@ -783,7 +1925,7 @@ fn format_submission_result(result: &crate::models::SubmissionResult) -> String
         if let Some(percentile) = result.memory_percentile {
             output.push_str(&format!(" (beats {:.1}% of submissions)", percentile));
         }
-        output.push_str("\n");
+        output.push('\n');
     }
     
     // Error details
@@ -839,7 +1981,7 @@ fn format_submission_result(result: &crate::models::SubmissionResult) -> String
 #[cfg(test)]
 mod test_functionality_tests {
     use super::*;
-    use crate::models::{TestResult, TestStatus};
+    use crate::models::{test_problem, CaseOutcome, TestResult, TestStatus};
 
     #[test]
     fn test_extract_problem_info_from_path() {
@@ -861,6 +2003,47 @@ mod test_functionality_tests {
         assert_eq!(lang4, "cpp");
     }
 
+    #[test]
+    fn test_parse_test_options_input() {
+        let args: Vec<String> = ["1-two-sum.rs", "--input", "[2,7,11,15]\\n9"]
+            .iter().map(|s| s.to_string()).collect();
+        let options = parse_test_options(&args).unwrap();
+        assert_eq!(options.file_path.as_deref(), Some("1-two-sum.rs"));
+        assert_eq!(options.data_input.as_deref(), Some("[2,7,11,15]\n9"));
+
+        // Input split across tokens is rejoined
+        let args: Vec<String> = ["--input", "\"hello", "world\""]
+            .iter().map(|s| s.to_string()).collect();
+        assert_eq!(parse_test_options(&args).unwrap().data_input.as_deref(), Some("\"hello world\""));
+
+        let args = vec!["--input-file".to_string()];
+        assert!(parse_test_options(&args).unwrap_err().contains("--input-file requires a path"));
+    }
+
+    #[test]
+    fn test_parse_test_options_input_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let input_path = temp_dir.path().join("case.txt");
+        std::fs::write(&input_path, "[3,3]\n6\n").unwrap();
+
+        let args = vec!["--input-file".to_string(), input_path.to_string_lossy().to_string()];
+        let options = parse_test_options(&args).unwrap();
+        assert!(options.file_path.is_none());
+        assert_eq!(options.data_input.as_deref(), Some("[3,3]\n6"));
+
+        let both = vec![
+            "--input".to_string(), "[1]".to_string(),
+            "--input-file".to_string(), input_path.to_string_lossy().to_string(),
+        ];
+        assert!(parse_test_options(&both).unwrap_err().contains("not both"));
+    }
+
+    #[test]
+    fn test_parse_test_options_defaults() {
+        assert_eq!(parse_test_options(&[]).unwrap(), TestOptions::default());
+        assert!(parse_test_options(&["--inptu".to_string()]).unwrap_err().contains("Unknown option"));
+    }
+
     #[test]
     fn test_extract_problem_info_unsupported_extension() {
         let result = extract_problem_info_from_path("test.xyz");
@@ -878,6 +2061,8 @@ mod test_functionality_tests {
             total_tests: 5,
             failed_test_case: None,
             compile_error: None,
+            cases: vec![],
+            stdout: None,
         };
 
         let output = format_test_result(&result);
@@ -898,6 +2083,8 @@ mod test_functionality_tests {
             total_tests: 5,
             failed_test_case: Some("Input: [1,2,3]\nExpected: [1,3]\nActual: [1,2]".to_string()),
             compile_error: None,
+            cases: vec![],
+            stdout: None,
         };
 
         let output = format_test_result(&result);
@@ -907,6 +2094,101 @@ mod test_functionality_tests {
         assert!(output.contains("Input: [1,2,3]"));
     }
 
+    #[test]
+    fn test_format_test_result_cases() {
+        let result = TestResult {
+            status: TestStatus::WrongAnswer,
+            runtime: Some(0),
+            memory: None,
+            passed_tests: 1,
+            total_tests: 2,
+            failed_test_case: Some("Input: [2,7,11,15]\n9".to_string()),
+            compile_error: None,
+            cases: vec![
+                CaseOutcome {
+                    input: "[2,7,11,15]\n9".to_string(),
+                    expected: "[0,1]".to_string(),
+                    actual: "[0,1]".to_string(),
+                    stdout: None,
+                    passed: true,
+                },
+                CaseOutcome {
+                    input: "[3,2,4]\n6".to_string(),
+                    expected: "[1,2]".to_string(),
+                    actual: "[2,1]".to_string(),
+                    stdout: None,
+                    passed: false,
+                },
+            ],
+            stdout: None,
+        };
+
+        let output = format_test_result(&result);
+        assert!(output.contains("| 1 | ✅ | `[2,7,11,15]` `9` | `[0,1]` | `[0,1]` |"));
+        assert!(output.contains("| 2 | ❌ | `[3,2,4]` `6` | `[1,2]` | `[2,1]` |"));
+        assert!(output.contains("### Case 2 Diff\n\n```diff\n- [1,2]\n+ [2,1]\n```"));
+        assert!(!output.contains("Case 1 Diff"));
+        assert!(!output.contains("Failed Test Case"));
+    }
+
+    #[test]
+    fn test_build_test_output_stdout_section() {
+        let mut result = TestResult {
+            status: TestStatus::Success,
+            runtime: None,
+            memory: None,
+            passed_tests: 2,
+            total_tests: 2,
+            failed_test_case: None,
+            compile_error: None,
+            cases: vec![],
+            stdout: Some("combined\n".to_string()),
+        };
+
+        let output = build_test_output(&result);
+        assert_eq!(output.sections.len(), 1);
+        let range = &output.sections[0].range;
+        let section = &output.text[range.start as usize..range.end as usize];
+        assert!(section.starts_with("\n## Stdout"));
+        assert!(section.contains("**All cases**\n\n```\ncombined\n```"));
+
+        // Per-case stdout takes precedence over the combined output
+        result.cases = vec![CaseOutcome {
+            input: "[1]".to_string(),
+            expected: "1".to_string(),
+            actual: "1".to_string(),
+            stdout: Some("case one".to_string()),
+            passed: true,
+        }];
+        let output = build_test_output(&result);
+        assert!(output.text.contains("**Case 1**\n\n```\ncase one\n```"));
+        assert!(!output.text.contains("All cases"));
+
+        result.cases.clear();
+        result.stdout = None;
+        assert!(build_test_output(&result).sections.is_empty());
+    }
+
+    #[test]
+    fn test_truncate_output() {
+        assert_eq!(truncate_output("short"), "short");
+        assert_eq!(truncate_output("line 1\r\nline 2\n"), "line 1\nline 2");
+
+        let long = (1..=80).map(|i| i.to_string()).collect::<Vec<_>>().join("\n");
+        let truncated = truncate_output(&long);
+        assert!(truncated.starts_with("1\n2\n"));
+        assert!(truncated.ends_with("... truncated (50 of 80 lines shown)"));
+
+        let wide = "é".repeat(MAX_STDOUT_CHARS + 10);
+        assert_eq!(truncate_output(&wide).chars().filter(|c| *c == 'é').count(), MAX_STDOUT_CHARS);
+    }
+
+    #[test]
+    fn test_format_table_cell() {
+        assert_eq!(format_table_cell(""), "-");
+        assert_eq!(format_table_cell("\"a|b\""), "`\"a\\|b\"`");
+    }
+
     #[test]
     fn test_format_test_result_compile_error() {
         let result = TestResult {
@@ -917,6 +2199,8 @@ mod test_functionality_tests {
             total_tests: 0,
             failed_test_case: None,
             compile_error: Some("SyntaxError: invalid syntax".to_string()),
+            cases: vec![],
+            stdout: None,
         };
 
         let output = format_test_result(&result);
@@ -925,6 +2209,189 @@ mod test_functionality_tests {
         assert!(output.contains("SyntaxError: invalid syntax"));
     }
 
+    #[test]
+    fn test_format_problem_details_translated() {
+        let problem = crate::models::ProblemDetail {
+            id: "1".to_string(),
+            frontend_id: "1".to_string(),
+            title: "Two Sum".to_string(),
+            title_slug: "two-sum".to_string(),
+            content: "<p>Given an array</p>".to_string(),
+            difficulty: Difficulty::Easy,
+            tags: vec![],
+            code_snippets: vec![],
+            sample_test_case: String::new(),
+            example_testcases: None,
+            translated_title: Some("两数之和".to_string()),
+            translated_content: Some("<p>给定一个整数数组</p>".to_string()),
+        };
+
+        let output = format_problem_details(&problem, std::path::Path::new("1-two-sum.rs"), "rust");
+        assert!(output.contains("# 1. 两数之和 (Two Sum)"));
+        assert!(output.contains("给定一个整数数组"));
+        assert!(!output.contains("Given an array"));
+    }
+
+    #[test]
+    fn test_parse_list_filters_search() {
+        let args: Vec<String> = ["--search", "lru", "cache", "--difficulty", "medium"]
+            .iter().map(|s| s.to_string()).collect();
+        let filters = parse_list_filters(&args).unwrap();
+        assert_eq!(filters.search_keywords.as_deref(), Some("lru cache"));
+        assert_eq!(filters.difficulty, Some(Difficulty::Medium));
+
+        let error = parse_list_filters(&["--search".to_string(), "--limit".to_string(), "5".to_string()]).unwrap_err();
+        assert!(error.contains("--search requires keywords"));
+    }
+
+    #[test]
+    fn test_parse_list_filters_status_list_and_paging() {
+        let args: Vec<String> = ["--status", "solved", "--list", "wpwgkgt", "--page", "3", "--limit", "20"]
+            .iter().map(|s| s.to_string()).collect();
+        let filters = parse_list_filters(&args).unwrap();
+        assert_eq!(filters.status.as_deref(), Some("AC"));
+        assert_eq!(filters.list_id.as_deref(), Some("wpwgkgt"));
+        assert_eq!(filters.skip, Some(40));
+
+        let filters = parse_list_filters(&["--skip".to_string(), "75".to_string()]).unwrap();
+        assert_eq!(filters.skip, Some(75));
+
+        let to_args = |args: &[&str]| args.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert!(parse_list_filters(&to_args(&["--status", "done"])).unwrap_err().contains("Invalid status"));
+        assert!(parse_list_filters(&to_args(&["--page", "0"])).unwrap_err().contains("Invalid page"));
+        assert!(parse_list_filters(&to_args(&["--page", "2147483647", "--limit", "50"])).unwrap_err().contains("Invalid page"));
+        assert!(parse_list_filters(&to_args(&["--limit", "0"])).unwrap_err().contains("Invalid limit"));
+        assert!(parse_list_filters(&to_args(&["--limit", "-5"])).unwrap_err().contains("Invalid limit"));
+        assert!(parse_list_filters(&to_args(&["--page", "2", "--skip", "5"])).unwrap_err().contains("not both"));
+    }
+
+    #[test]
+    fn test_parse_list_filters_sort() {
+        let to_args = |args: &[&str]| args.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        let filters = parse_list_filters(&to_args(&["--order", "desc", "--sort", "acceptance"])).unwrap();
+        assert_eq!(filters.sort_by, Some(SortField::Acceptance));
+        assert_eq!(filters.sort_order, SortOrder::Descending);
+
+        let filters = parse_list_filters(&to_args(&["--sort", "difficulty"])).unwrap();
+        assert_eq!(filters.sort_order, SortOrder::Ascending);
+
+        assert!(parse_list_filters(&to_args(&["--order", "desc"])).unwrap_err().contains("--order requires --sort"));
+        assert!(parse_list_filters(&to_args(&["--sort", "likes"])).unwrap_err().contains("Invalid sort"));
+
+        let filters = parse_list_filters(&to_args(&["--sort", "frequency"])).unwrap();
+        assert!(sorted_catalog_list(&filters, SortField::Frequency, Site::China).unwrap_err().contains("not supported on"));
+    }
+
+    #[test]
+    fn test_format_problems_list_footer() {
+        let problem = crate::models::Problem {
+            id: "1".to_string(),
+            frontend_id: "1".to_string(),
+            title: "Two Sum".to_string(),
+            title_slug: "two-sum".to_string(),
+            difficulty: Difficulty::Easy,
+            tags: vec![],
+            is_paid_only: false,
+            acceptance_rate: 0.5,
+            translated_title: None,
+            status: Some("ac".to_string()),
+        };
+        let filters = ProblemFilters {
+            difficulty: Some(Difficulty::Easy),
+            status: Some("NOT_STARTED".to_string()),
+            limit: Some(1),
+            ..Default::default()
+        };
+        let page = ProblemPage { total: 12, skip: 10, limit: 1, questions: vec![problem] };
+
+        let output = format_problems_list(&page, &filters);
+        assert!(output.contains("- Status: todo"));
+        assert!(output.contains("**Page 11 of 12** · showing 11–11 of 12 problems · ✅ 1/1 solved on this page"));
+        assert!(output.contains("| ✅ | 1 | Two Sum |"));
+        assert!(output.contains("Previous page: `/leetcode-list --difficulty easy --status todo --limit 1 --page 10`"));
+        assert!(output.contains("Next page: `/leetcode-list --difficulty easy --status todo --limit 1 --page 12`"));
+
+        let last_page = ProblemPage { skip: 11, ..page.clone() };
+        assert!(!format_problems_list(&last_page, &filters).contains("Next page"));
+    }
+
+    #[test]
+    fn test_list_command_unaligned_skip() {
+        let filters = ProblemFilters::default();
+        assert_eq!(list_command(&filters, 100, 50), "/leetcode-list --page 3");
+        assert_eq!(list_command(&filters, 75, 50), "/leetcode-list --skip 75");
+    }
+
+    #[test]
+    fn test_format_sync_summary_and_pick_random() {
+        let catalog = ProblemCatalog::new(Site::Global, vec![
+            Problem { status: Some("ac".to_string()), ..test_problem("1") },
+            Problem { status: Some("notac".to_string()), ..test_problem("2") },
+            Problem { is_paid_only: true, ..test_problem("3") },
+            test_problem("4"),
+        ]);
+
+        let summary = format_sync_summary(&catalog);
+        assert!(summary.contains("Synced 4 problems from leetcode.com"));
+        assert!(summary.contains("- Solved: 1"));
+        assert!(summary.contains("- Attempted: 1"));
+        assert!(summary.contains("- Premium: 1"));
+
+        let filters = ProblemFilters {
+            status: Some("NOT_STARTED".to_string()),
+            premium_only: Some(false),
+            ..Default::default()
+        };
+        assert_eq!(pick_random(&catalog, &filters).map(|problem| problem.title_slug).as_deref(), Some("problem-4"));
+        assert_eq!(random_pick_header(&filters), "🎲 **Random pick** from unsolved problems\n\n");
+        let solved = ProblemFilters { status: Some("AC".to_string()), ..filters.clone() };
+        assert_eq!(random_pick_header(&solved), "🎲 **Random pick** from solved problems\n\n");
+        assert_eq!(no_random_match(&solved), "No solved problems match the given filters.");
+
+        let pick = format_catalog_pick(&test_problem("4"), Site::China);
+        assert!(pick.contains("# 4. Problem 4"));
+        assert!(pick.contains("https://leetcode.cn/problems/problem-4/"));
+        assert!(pick.contains("/leetcode-show problem-4"));
+
+        let filters = ProblemFilters { difficulty: Some(Difficulty::Hard), ..Default::default() };
+        assert!(pick_random(&catalog, &filters).is_none());
+    }
+
+    #[test]
+    fn test_format_index_search() {
+        let entry = crate::models::ProblemIndexEntry {
+            frontend_id: "146".to_string(),
+            title: "LRU Cache".to_string(),
+            title_slug: "lru-cache".to_string(),
+        };
+
+        let output = format_index_search(&[&entry], "lru", OfflineReason::Unreachable);
+        assert!(output.contains("LeetCode is unreachable; results come from the cached problem index"));
+        assert!(output.contains("| 146 | LRU Cache | `lru-cache` |"));
+
+        let output = format_index_search(&[], "graph", OfflineReason::NotSignedIn);
+        assert!(output.contains("You are not logged in"));
+        assert!(output.contains("No cached problems match \"graph\""));
+    }
+
+    #[test]
+    fn test_format_daily_header() {
+        let mut challenge = crate::models::DailyChallenge {
+            date: "2024-01-15".to_string(),
+            user_status: Some("NotStart".to_string()),
+            link: Some("/problems/two-sum/".to_string()),
+            question: crate::models::QuestionSlug { title_slug: "two-sum".to_string() },
+        };
+
+        let output = format_daily_header(&challenge);
+        assert!(output.contains("2024-01-15"));
+        assert!(output.contains("Not completed yet"));
+
+        challenge.user_status = Some("Finish".to_string());
+        assert!(format_daily_header(&challenge).contains("✅ Completed"));
+    }
+
     #[test]
     fn test_format_submission_result_accepted() {
         let result = crate::models::SubmissionResult {
@@ -991,4 +2458,66 @@ mod test_functionality_tests {
         assert!(output.contains("**Test Cases Passed:** 50/100"));
         assert!(output.contains("optimizing your algorithm's time complexity"));
     }
+
+    #[test]
+    fn test_validate_profile_name() {
+        assert_eq!(validate_profile_name("work-premium_2").unwrap(), "work-premium_2");
+        assert!(validate_profile_name("").is_err());
+        assert!(validate_profile_name("my work").is_err());
+        assert!(validate_profile_name("../work").is_err());
+    }
+
+    #[test]
+    fn test_format_profiles() {
+        let mut personal = Profile::new("personal", "LEETCODE_SESSION=a");
+        personal.site = Some(Site::Global);
+        personal.username = Some("alice".to_string());
+        let mut work = Profile::new("work", "LEETCODE_SESSION=b");
+        work.site = Some(Site::China);
+
+        let output = format_profiles(&[personal.clone(), work.clone()], Some("work"));
+        assert!(output.contains("|  | personal | alice | leetcode.com |"));
+        assert!(output.contains("| ▶ | work | - | leetcode.cn |"));
+        assert!(!output.contains("SESSION"));
+
+        assert!(format_profiles(&[], None).contains("No saved profiles"));
+        assert_eq!(profile_account(&personal), "as alice on leetcode.com");
+        assert_eq!(profile_account(&work), "to leetcode.cn");
+    }
+
+    #[test]
+    fn test_handle_profile_usage() {
+        assert!(handle_profile(vec!["use".to_string()]).unwrap_err().contains("Usage"));
+        assert!(handle_profile(vec!["rename".to_string(), "a".to_string()]).unwrap_err().contains("Usage"));
+    }
+
+    #[test]
+    fn test_format_whoami() {
+        let user_status = UserStatus {
+            is_signed_in: true,
+            username: Some("alice".to_string()),
+            is_premium: Some(true),
+            avatar: Some("https://assets.leetcode.com/alice.png".to_string()),
+            real_name: Some("Alice Liddell".to_string()),
+        };
+        let output = format_whoami(&user_status, "work", Site::China);
+        assert!(output.starts_with("# 👤 alice"));
+        assert!(output.contains("**Name:** Alice Liddell"));
+        assert!(output.contains("**Site:** leetcode.cn"));
+        assert!(output.contains("**Profile:** work"));
+        assert!(output.contains("**Premium:** ⭐ Yes"));
+        assert!(output.contains("**Avatar:** https://assets.leetcode.com/alice.png"));
+
+        let minimal = UserStatus { is_signed_in: true, username: Some("bob".to_string()), ..UserStatus::default() };
+        let output = format_whoami(&minimal, "default", Site::Global);
+        assert!(!output.contains("**Name:**"));
+        assert!(!output.contains("**Premium:**"));
+    }
+
+    #[test]
+    fn test_handle_logout_usage() {
+        let args = vec!["a".to_string(), "b".to_string()];
+        assert!(handle_logout(args).unwrap_err().contains("Usage"));
+        assert!(handle_logout(vec!["--everything".to_string()]).unwrap_err().contains("Usage"));
+    }
 }
@@ -0,0 +1,248 @@
+use anyhow::{anyhow, bail, Context, Result};
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use std::ffi::OsString;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+/// Directory name used under the user's config directory
+const APP_DIR: &str = "zed-leetcode";
+const KEY_FILE: &str = "secret.key";
+/// Prefix marking a value produced by `encrypt`
+const CIPHERTEXT_PREFIX: &str = "v1:";
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+
+/// Per-user config directory for the extension
+///
+/// `$XDG_CONFIG_HOME/zed-leetcode`, falling back to `~/.config/zed-leetcode`.
+/// `None` when neither variable is set (e.g. inside a sandbox without a home).
+#[cfg(not(test))]
+pub fn user_config_dir() -> Option<PathBuf> {
+    config_dir_from(std::env::var_os("XDG_CONFIG_HOME"), std::env::var_os("HOME"))
+}
+
+/// Unit tests get a fresh directory per test thread instead of the
+/// developer's real config
+#[cfg(test)]
+pub fn user_config_dir() -> Option<PathBuf> {
+    thread_local! {
+        static CONFIG_HOME: tempfile::TempDir = tempfile::TempDir::new().expect("failed to create test config dir");
+    }
+    CONFIG_HOME.with(|dir| config_dir_from(Some(dir.path().into()), None))
+}
+
+fn config_dir_from(xdg_config_home: Option<OsString>, home: Option<OsString>) -> Option<PathBuf> {
+    let base = xdg_config_home
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            home.filter(|dir| !dir.is_empty())
+                .map(|home| PathBuf::from(home).join(".config"))
+        })?;
+    Some(base.join(APP_DIR))
+}
+
+/// Default location of the machine-local key, outside any worktree
+pub fn default_key_path() -> Option<PathBuf> {
+    user_config_dir().map(|dir| dir.join(KEY_FILE))
+}
+
+/// Random number from the operating system's generator
+///
+/// Used for jitter and random picks, where clock-derived values are too
+/// coarse on some platforms.
+pub fn random_u32() -> u32 {
+    OsRng.next_u32()
+}
+
+/// Whether a stored value was produced by `encrypt`
+pub fn is_encrypted(value: &str) -> bool {
+    value.starts_with(CIPHERTEXT_PREFIX)
+}
+
+/// Symmetric key used to encrypt credentials at rest
+pub struct SecretKey {
+    cipher: ChaCha20Poly1305,
+}
+
+impl SecretKey {
+    /// Read the key at `path`, or `None` if it has not been created yet
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let encoded = fs::read_to_string(path)
+            .context("Failed to read encryption key")?;
+        let bytes = BASE64.decode(encoded.trim())
+            .context("Encryption key is corrupted")?;
+        if bytes.len() != KEY_LEN {
+            bail!("Encryption key is corrupted");
+        }
+
+        Ok(Some(Self { cipher: ChaCha20Poly1305::new(Key::from_slice(&bytes)) }))
+    }
+
+    /// Read the key at `path`, generating and saving a new one if missing
+    ///
+    /// The file is created exclusively with 0600 permissions, so it is never
+    /// readable by others and a concurrent writer's key wins over ours.
+    pub fn load_or_create(path: &Path) -> Result<Self> {
+        if let Some(key) = Self::load(path)? {
+            return Ok(key);
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .context("Failed to create key directory")?;
+            restrict_permissions(parent, 0o700)?;
+        }
+
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+
+        let mut file = match options.open(path) {
+            Ok(file) => file,
+            Err(error) if error.kind() == ErrorKind::AlreadyExists => {
+                return Self::load(path)?
+                    .ok_or_else(|| anyhow!("Encryption key disappeared while it was being created"));
+            },
+            Err(error) => return Err(error).context("Failed to create encryption key"),
+        };
+
+        let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+        file.write_all(BASE64.encode(key).as_bytes())
+            .context("Failed to write encryption key")?;
+
+        Ok(Self { cipher: ChaCha20Poly1305::new(&key) })
+    }
+
+    /// Encrypt `plaintext` with a fresh random nonce
+    pub fn encrypt(&self, plaintext: &str) -> Result<String> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self.cipher.encrypt(&nonce, plaintext.as_bytes())
+            .map_err(|_| anyhow!("Failed to encrypt credential"))?;
+
+        let mut payload = nonce.to_vec();
+        payload.extend_from_slice(&ciphertext);
+        Ok(format!("{}{}", CIPHERTEXT_PREFIX, BASE64.encode(payload)))
+    }
+
+    /// Decrypt a value produced by `encrypt`, rejecting anything tampered with
+    pub fn decrypt(&self, value: &str) -> Result<String> {
+        let encoded = value.strip_prefix(CIPHERTEXT_PREFIX)
+            .ok_or_else(|| anyhow!("Credential is not encrypted"))?;
+        let payload = BASE64.decode(encoded)
+            .context("Encrypted credential is corrupted")?;
+        if payload.len() < NONCE_LEN {
+            bail!("Encrypted credential is corrupted");
+        }
+
+        let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+        let plaintext = self.cipher.decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| anyhow!("Failed to decrypt credential; the encryption key has changed, please login again"))?;
+
+        String::from_utf8(plaintext)
+            .context("Invalid UTF-8 in decrypted credential")
+    }
+}
+
+/// Set Unix permissions on `path` (no-op elsewhere)
+fn restrict_permissions(path: &Path, mode: u32) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(mode))
+            .context("Failed to restrict permissions")?;
+    }
+    #[cfg(not(unix))]
+    let _ = (path, mode);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_config_dir_from() {
+        assert_eq!(
+            config_dir_from(Some("/xdg".into()), Some("/home/me".into())),
+            Some(PathBuf::from("/xdg/zed-leetcode"))
+        );
+        assert_eq!(
+            config_dir_from(Some("".into()), Some("/home/me".into())),
+            Some(PathBuf::from("/home/me/.config/zed-leetcode"))
+        );
+        assert_eq!(config_dir_from(None, None), None);
+    }
+
+    #[test]
+    fn test_encrypt_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let key = SecretKey::load_or_create(&temp_dir.path().join("keys").join(KEY_FILE)).unwrap();
+
+        let encrypted = key.encrypt("LEETCODE_SESSION=abc").unwrap();
+        assert!(is_encrypted(&encrypted));
+        assert!(!encrypted.contains("LEETCODE_SESSION"));
+        assert_ne!(encrypted, key.encrypt("LEETCODE_SESSION=abc").unwrap());
+        assert_eq!(key.decrypt(&encrypted).unwrap(), "LEETCODE_SESSION=abc");
+    }
+
+    #[test]
+    fn test_key_is_persisted() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(KEY_FILE);
+        assert!(SecretKey::load(&path).unwrap().is_none());
+
+        let encrypted = SecretKey::load_or_create(&path).unwrap().encrypt("secret").unwrap();
+        let reloaded = SecretKey::load(&path).unwrap().unwrap();
+        assert_eq!(reloaded.decrypt(&encrypted).unwrap(), "secret");
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+
+    #[test]
+    fn test_existing_key_is_not_replaced() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(KEY_FILE);
+
+        let first = SecretKey::load_or_create(&path).unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        let second = SecretKey::load_or_create(&path).unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), contents);
+        assert_eq!(second.decrypt(&first.encrypt("secret").unwrap()).unwrap(), "secret");
+    }
+
+    #[test]
+    fn test_decrypt_rejects_wrong_key_and_tampering() {
+        let temp_dir = TempDir::new().unwrap();
+        let key = SecretKey::load_or_create(&temp_dir.path().join("a.key")).unwrap();
+        let other = SecretKey::load_or_create(&temp_dir.path().join("b.key")).unwrap();
+
+        let encrypted = key.encrypt("secret").unwrap();
+        assert!(other.decrypt(&encrypted).is_err());
+
+        let mut tampered = encrypted.clone();
+        let last = tampered.pop().unwrap();
+        tampered.push(if last == 'A' { 'B' } else { 'A' });
+        assert!(key.decrypt(&tampered).is_err());
+        assert!(key.decrypt("c2VjcmV0").is_err());
+    }
+}
@@ -0,0 +1,151 @@
+use crate::models::GraphQLError;
+use std::time::Duration;
+
+/// Errors returned by `LeetCodeApi`
+#[derive(Debug, Clone, PartialEq)]
+pub enum LeetCodeError {
+    /// No session, or the session was rejected by LeetCode
+    Unauthenticated,
+    /// CSRF token missing locally or rejected by LeetCode
+    CsrfMismatch,
+    /// LeetCode is throttling requests (HTTP 429)
+    RateLimited { retry_after: Option<Duration> },
+    /// Problem or feature is only available to Premium subscribers
+    PremiumRequired,
+    /// Requested problem or resource does not exist
+    NotFound(String),
+    /// Request never got a usable HTTP response (curl failure, 5xx, etc.)
+    Network(String),
+    /// Judge did not produce a result in time
+    Judge(String),
+    /// Response could not be parsed into the expected shape
+    Parse(String),
+    /// Any other error reported by the GraphQL endpoint
+    Api(String),
+}
+
+/// Result type used throughout the API layer
+pub type Result<T> = std::result::Result<T, LeetCodeError>;
+
+impl LeetCodeError {
+    /// Classify an unsuccessful HTTP status
+    ///
+    /// A 403 on a request that carried a CSRF token is a CSRF rejection;
+    /// otherwise LeetCode uses it for expired sessions. Only 5xx counts as a
+    /// network problem; other client errors are reported as API errors.
+    pub fn from_status(status: u16, csrf_sent: bool) -> Self {
+        match status {
+            401 => LeetCodeError::Unauthenticated,
+            403 if csrf_sent => LeetCodeError::CsrfMismatch,
+            403 => LeetCodeError::Unauthenticated,
+            404 => LeetCodeError::NotFound("LeetCode returned HTTP 404".to_string()),
+            429 => LeetCodeError::RateLimited { retry_after: None },
+            500..=599 => LeetCodeError::Network(format!("LeetCode returned HTTP {}", status)),
+            _ => LeetCodeError::Api(format!("LeetCode returned HTTP {}", status)),
+        }
+    }
+
+    /// Whether repeating the same request might succeed
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, LeetCodeError::RateLimited { .. } | LeetCodeError::Network(_))
+    }
+
+    /// Classify GraphQL `errors` by their messages
+    pub fn from_graphql_errors(errors: &[GraphQLError]) -> Self {
+        let message = errors.iter()
+            .map(|error| error.message.as_str())
+            .collect::<Vec<_>>()
+            .join("; ");
+        let lower = message.to_lowercase();
+
+        if lower.contains("premium") || lower.contains("subscribe") {
+            LeetCodeError::PremiumRequired
+        } else if lower.contains("rate limit") || lower.contains("too many requests") {
+            LeetCodeError::RateLimited { retry_after: None }
+        } else if lower.contains("not logged in") || lower.contains("login required") || lower.contains("authenticat") {
+            LeetCodeError::Unauthenticated
+        } else if lower.contains("not found") || lower.contains("does not exist") {
+            LeetCodeError::NotFound(message)
+        } else {
+            LeetCodeError::Api(message)
+        }
+    }
+}
+
+impl std::fmt::Display for LeetCodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LeetCodeError::Unauthenticated => write!(f, "Authentication required"),
+            LeetCodeError::CsrfMismatch => write!(f, "CSRF token missing or rejected"),
+            LeetCodeError::RateLimited { retry_after: Some(delay) } => {
+                write!(f, "Rate limited by LeetCode, retry after {}s", delay.as_secs())
+            },
+            LeetCodeError::RateLimited { retry_after: None } => write!(f, "Rate limited by LeetCode"),
+            LeetCodeError::PremiumRequired => write!(f, "LeetCode Premium required"),
+            LeetCodeError::NotFound(what) => write!(f, "Not found: {}", what),
+            LeetCodeError::Network(message) => write!(f, "Network error: {}", message),
+            LeetCodeError::Judge(message) => write!(f, "Judge error: {}", message),
+            LeetCodeError::Parse(message) => write!(f, "Unexpected response: {}", message),
+            LeetCodeError::Api(message) => write!(f, "LeetCode error: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for LeetCodeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graphql_error(message: &str) -> Vec<GraphQLError> {
+        vec![GraphQLError { message: message.to_string() }]
+    }
+
+    #[test]
+    fn test_from_status() {
+        assert_eq!(LeetCodeError::from_status(401, false), LeetCodeError::Unauthenticated);
+        assert_eq!(LeetCodeError::from_status(403, true), LeetCodeError::CsrfMismatch);
+        assert_eq!(LeetCodeError::from_status(403, false), LeetCodeError::Unauthenticated);
+        assert_eq!(LeetCodeError::from_status(429, false), LeetCodeError::RateLimited { retry_after: None });
+        assert!(matches!(LeetCodeError::from_status(404, false), LeetCodeError::NotFound(_)));
+        assert!(LeetCodeError::from_status(502, false).to_string().contains("HTTP 502"));
+        assert!(matches!(LeetCodeError::from_status(502, false), LeetCodeError::Network(_)));
+        assert_eq!(LeetCodeError::from_status(400, false), LeetCodeError::Api("LeetCode returned HTTP 400".to_string()));
+        assert!(!LeetCodeError::from_status(422, false).is_retryable());
+    }
+
+    #[test]
+    fn test_from_graphql_errors() {
+        assert_eq!(
+            LeetCodeError::from_graphql_errors(&graphql_error("You need to subscribe to Premium")),
+            LeetCodeError::PremiumRequired
+        );
+        assert_eq!(
+            LeetCodeError::from_graphql_errors(&graphql_error("rate limited")),
+            LeetCodeError::RateLimited { retry_after: None }
+        );
+        assert!(matches!(
+            LeetCodeError::from_graphql_errors(&graphql_error("Question does not exist")),
+            LeetCodeError::NotFound(_)
+        ));
+        assert_eq!(
+            LeetCodeError::from_graphql_errors(&graphql_error("boom")),
+            LeetCodeError::Api("boom".to_string())
+        );
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        assert!(LeetCodeError::RateLimited { retry_after: None }.is_retryable());
+        assert!(LeetCodeError::from_status(503, false).is_retryable());
+        assert!(!LeetCodeError::Unauthenticated.is_retryable());
+        assert!(!LeetCodeError::Parse("bad".to_string()).is_retryable());
+    }
+
+    #[test]
+    fn test_display() {
+        let error = LeetCodeError::RateLimited { retry_after: Some(Duration::from_secs(30)) };
+        assert_eq!(error.to_string(), "Rate limited by LeetCode, retry after 30s");
+        assert_eq!(LeetCodeError::Judge("timed out".to_string()).to_string(), "Judge error: timed out");
+    }
+}
@@ -1,7 +1,8 @@
-use crate::models::{Problem, ProblemDetail};
+use crate::models::{Problem, ProblemCatalog, ProblemDetail, ProblemIndex, Site};
 use crate::templates::TemplateGenerator;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use zed_extension_api::Worktree;
@@ -10,12 +11,22 @@ const LEETCODE_DIR: &str = ".leetcode";
 const PROBLEMS_DIR: &str = "problems";
 const SOLUTIONS_DIR: &str = "solutions";
 const CONFIG_FILE: &str = "config.json";
+const PROBLEM_INDEX_FILE: &str = "problem_index.json";
+const CATALOG_FILE: &str = "catalog.json";
 
 /// Configuration stored in .leetcode/config.json
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     pub session_cookie: Option<String>,
     pub default_language: String,
+    #[serde(default)]
+    pub site: Site,
+    /// Seconds to wait for the judge before giving up on a test run or submission
+    #[serde(default = "default_judge_timeout_secs")]
+    pub judge_timeout_secs: u64,
+    /// Seconds a successful session check is trusted before asking LeetCode again
+    #[serde(default = "default_session_trust_secs")]
+    pub session_trust_secs: u64,
     pub created_at: String,
     pub last_updated: String,
 }
@@ -26,12 +37,63 @@ impl Default for Config {
         Self {
             session_cookie: None,
             default_language: "rust".to_string(),
+            site: Site::default(),
+            judge_timeout_secs: default_judge_timeout_secs(),
+            session_trust_secs: default_session_trust_secs(),
             created_at: now.clone(),
             last_updated: now,
         }
     }
 }
 
+/// Per-user settings stored in config.json next to the saved credentials
+///
+/// These decide where the session is written, so they are never read from a
+/// workspace: a cloned repository can't opt itself into receiving it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UserConfig {
+    /// Credentials directory for a workspace root, instead of the per-user
+    /// directory; relative paths are resolved against that root
+    #[serde(default)]
+    pub credentials_dirs: BTreeMap<PathBuf, PathBuf>,
+    /// Allow saving credentials and their key into a directory git would pick up
+    #[serde(default)]
+    pub allow_credentials_in_git: bool,
+}
+
+impl UserConfig {
+    /// Read config.json from the per-user config directory without creating it
+    pub fn read(user_config_dir: &Path) -> Result<Option<Self>> {
+        let config_path = user_config_dir.join(CONFIG_FILE);
+
+        if !config_path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&config_path)
+            .with_context(|| format!("Failed to read config from {:?}", config_path))?;
+
+        let config: UserConfig = serde_json::from_str(&content)
+            .context("Failed to deserialize user config")?;
+
+        Ok(Some(config))
+    }
+
+    /// Credentials directory configured for `workspace_root`, if any
+    pub fn credentials_dir_for(&self, workspace_root: &Path) -> Option<PathBuf> {
+        self.credentials_dirs.get(workspace_root)
+            .map(|dir| workspace_root.join(dir))
+    }
+}
+
+fn default_judge_timeout_secs() -> u64 {
+    30
+}
+
+fn default_session_trust_secs() -> u64 {
+    24 * 60 * 60
+}
+
 /// File system manager for LeetCode problems and solutions
 pub struct FileManager {
     workspace_root: PathBuf,
@@ -40,7 +102,7 @@ pub struct FileManager {
 
 impl FileManager {
     /// Create new FileManager with workspace root from Worktree
-    pub fn new(worktree: &Worktree) -> Result<Self> {
+    pub fn new(_worktree: &Worktree) -> Result<Self> {
         // For now, use a placeholder path until we can access Worktree path properly
         // This will be fixed when we have proper Worktree API documentation
         let workspace_root = PathBuf::from("/tmp/leetcode-workspace"); // Placeholder
@@ -56,8 +118,7 @@ impl FileManager {
         Ok(manager)
     }
 
-    /// Create FileManager for testing with custom path
-    #[cfg(test)]
+    /// Create FileManager rooted at a custom path (workspace directory or test fixture)
     pub fn new_with_path(path: PathBuf) -> Result<Self> {
         let leetcode_dir = path.join(LEETCODE_DIR);
         let manager = Self {
@@ -68,6 +129,11 @@ impl FileManager {
         Ok(manager)
     }
 
+    /// Get the workspace root path
+    pub fn get_workspace_root(&self) -> &Path {
+        &self.workspace_root
+    }
+
     /// Get the .leetcode directory path
     pub fn get_leetcode_dir(&self) -> &Path {
         &self.leetcode_dir
@@ -141,6 +207,66 @@ impl FileManager {
         Ok(Some(problem))
     }
 
+    /// Save the frontend ID / title → slug index
+    pub fn save_problem_index(&self, index: &ProblemIndex) -> Result<()> {
+        let filepath = self.leetcode_dir.join(PROBLEM_INDEX_FILE);
+
+        let json = serde_json::to_string_pretty(index)
+            .context("Failed to serialize problem index")?;
+
+        fs::write(&filepath, json)
+            .with_context(|| format!("Failed to write problem index to {:?}", filepath))?;
+
+        Ok(())
+    }
+
+    /// Load the frontend ID / title → slug index
+    pub fn load_problem_index(&self) -> Result<Option<ProblemIndex>> {
+        let filepath = self.leetcode_dir.join(PROBLEM_INDEX_FILE);
+
+        if !filepath.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&filepath)
+            .with_context(|| format!("Failed to read problem index from {:?}", filepath))?;
+
+        let index: ProblemIndex = serde_json::from_str(&content)
+            .context("Failed to deserialize problem index")?;
+
+        Ok(Some(index))
+    }
+
+    /// Save the full problem catalog written by /leetcode-sync
+    pub fn save_catalog(&self, catalog: &ProblemCatalog) -> Result<()> {
+        let filepath = self.leetcode_dir.join(CATALOG_FILE);
+
+        let json = serde_json::to_string(catalog)
+            .context("Failed to serialize problem catalog")?;
+
+        fs::write(&filepath, json)
+            .with_context(|| format!("Failed to write problem catalog to {:?}", filepath))?;
+
+        Ok(())
+    }
+
+    /// Load the full problem catalog, if it has been synced
+    pub fn load_catalog(&self) -> Result<Option<ProblemCatalog>> {
+        let filepath = self.leetcode_dir.join(CATALOG_FILE);
+
+        if !filepath.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&filepath)
+            .with_context(|| format!("Failed to read problem catalog from {:?}", filepath))?;
+
+        let catalog: ProblemCatalog = serde_json::from_str(&content)
+            .context("Failed to deserialize problem catalog")?;
+
+        Ok(Some(catalog))
+    }
+
     /// Create solution file with template
     pub fn create_solution_file(&self, problem: &ProblemDetail, language: &str) -> Result<PathBuf> {
         let solutions_dir = self.leetcode_dir.join(SOLUTIONS_DIR);
@@ -197,12 +323,21 @@ impl FileManager {
         Ok(config)
     }
 
-    /// Update session cookie in config
-    pub fn update_session_cookie(&self, session_cookie: String) -> Result<()> {
-        let mut config = self.load_config()?;
-        config.session_cookie = Some(session_cookie);
-        config.last_updated = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC").to_string();
-        self.save_config(&config)
+    /// Read config from a .leetcode directory without creating any files
+    pub fn read_config(leetcode_dir: &Path) -> Result<Option<Config>> {
+        let config_path = leetcode_dir.join(CONFIG_FILE);
+
+        if !config_path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&config_path)
+            .with_context(|| format!("Failed to read config from {:?}", config_path))?;
+
+        let config: Config = serde_json::from_str(&content)
+            .context("Failed to deserialize config")?;
+
+        Ok(Some(config))
     }
 
     /// List all cached problems
@@ -219,13 +354,13 @@ impl FileManager {
             let entry = entry?;
             let path = entry.path();
             
-            if let Some(filename) = path.file_name() {
-                if let Some(name) = filename.to_str() {
-                    if name.ends_with(".json") && !name.contains("-detail") {
-                        let id = name.replace(".json", "");
-                        problem_ids.push(id);
-                    }
-                }
+            if let Some(filename) = path.file_name()
+                && let Some(name) = filename.to_str()
+                && name.ends_with(".json")
+                && !name.contains("-detail")
+            {
+                let id = name.replace(".json", "");
+                problem_ids.push(id);
             }
         }
         
@@ -293,7 +428,7 @@ impl FileManager {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{Difficulty, Tag, CodeSnippet};
+    use crate::models::{Difficulty, Tag, CodeSnippet, ProblemIndexEntry};
     use tempfile::TempDir;
 
     fn create_test_problem() -> Problem {
@@ -308,6 +443,8 @@ mod tests {
             ],
             is_paid_only: false,
             acceptance_rate: 49.5,
+            translated_title: None,
+            status: None,
         }
     }
 
@@ -331,6 +468,8 @@ mod tests {
             ],
             sample_test_case: "nums = [2,7,11,15], target = 9".to_string(),
             example_testcases: None,
+            translated_title: None,
+            translated_content: None,
         }
     }
 
@@ -378,6 +517,37 @@ mod tests {
         assert!(!loaded_problem.code_snippets.is_empty());
     }
 
+    #[test]
+    fn test_save_and_load_problem_index() {
+        let temp_dir = TempDir::new().unwrap();
+        let fm = FileManager::new_with_path(temp_dir.path().to_path_buf()).unwrap();
+
+        // Initially no index
+        assert!(fm.load_problem_index().unwrap().is_none());
+
+        let index = ProblemIndex::new(vec![ProblemIndexEntry::from(&create_test_problem())]);
+        fm.save_problem_index(&index).unwrap();
+
+        let loaded = fm.load_problem_index().unwrap().unwrap();
+        assert_eq!(loaded, index);
+        assert_eq!(loaded.find_by_frontend_id("1").unwrap().title_slug, "two-sum");
+    }
+
+    #[test]
+    fn test_save_and_load_catalog() {
+        let temp_dir = TempDir::new().unwrap();
+        let fm = FileManager::new_with_path(temp_dir.path().to_path_buf()).unwrap();
+
+        assert!(fm.load_catalog().unwrap().is_none());
+
+        let catalog = ProblemCatalog::new(Site::China, vec![create_test_problem()]);
+        fm.save_catalog(&catalog).unwrap();
+
+        let loaded = fm.load_catalog().unwrap().unwrap();
+        assert_eq!(loaded, catalog);
+        assert_eq!(loaded.site, Site::China);
+    }
+
     #[test]
     fn test_create_solution_file() {
         let temp_dir = TempDir::new().unwrap();
@@ -404,13 +574,47 @@ mod tests {
         let config = fm.load_config().unwrap();
         assert_eq!(config.default_language, "rust");
         assert!(config.session_cookie.is_none());
+    }
+
+    #[test]
+    fn test_site_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let fm = FileManager::new_with_path(temp_dir.path().to_path_buf()).unwrap();
 
-        // Update session cookie
-        fm.update_session_cookie("test_cookie".to_string()).unwrap();
+        // No config written yet
+        assert!(FileManager::read_config(fm.get_leetcode_dir()).unwrap().is_none());
 
-        // Load updated config
-        let updated_config = fm.load_config().unwrap();
-        assert_eq!(updated_config.session_cookie, Some("test_cookie".to_string()));
+        // Hand-edited site setting
+        let edited = r#"{"session_cookie": null, "default_language": "rust", "created_at": "x", "last_updated": "x", "site": "leetcode.cn"}"#;
+        fs::write(fm.get_leetcode_dir().join(CONFIG_FILE), edited).unwrap();
+        let config = FileManager::read_config(fm.get_leetcode_dir()).unwrap().unwrap();
+        assert_eq!(config.site, Site::China);
+
+        // Configs written before the site setting existed default to leetcode.com
+        let legacy = r#"{"session_cookie": null, "default_language": "rust", "created_at": "x", "last_updated": "x"}"#;
+        fs::write(fm.get_leetcode_dir().join(CONFIG_FILE), legacy).unwrap();
+        let config = fm.load_config().unwrap();
+        assert_eq!(config.site, Site::Global);
+        assert_eq!(config.session_trust_secs, 86400);
+        assert_eq!(config.judge_timeout_secs, 30);
+    }
+
+    #[test]
+    fn test_user_config() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(UserConfig::read(temp_dir.path()).unwrap().is_none());
+
+        let content = r#"{"credentials_dirs": {"/work/project": "../secrets", "/work/other": "/abs/creds"}}"#;
+        fs::write(temp_dir.path().join(CONFIG_FILE), content).unwrap();
+        let config = UserConfig::read(temp_dir.path()).unwrap().unwrap();
+
+        assert!(!config.allow_credentials_in_git);
+        assert_eq!(
+            config.credentials_dir_for(Path::new("/work/project")),
+            Some(PathBuf::from("/work/project/../secrets"))
+        );
+        assert_eq!(config.credentials_dir_for(Path::new("/work/other")), Some(PathBuf::from("/abs/creds")));
+        assert_eq!(config.credentials_dir_for(Path::new("/elsewhere")), None);
     }
 
     #[test]
@@ -6,6 +6,11 @@ pub mod api;
 pub mod auth;
 pub mod templates;
 pub mod file_manager;
+pub mod transport;
+pub mod resolver;
+pub mod error;
+pub mod rate_limit;
+pub mod crypto;
 
 struct LeetCodeExtension;
 
@@ -22,8 +27,14 @@ impl zed::Extension for LeetCodeExtension {
     ) -> Result<zed::SlashCommandOutput, String> {
         match command.name.as_str() {
             "leetcode-login" => commands::handle_login(args),
+            "leetcode-logout" => commands::handle_logout(args),
+            "leetcode-whoami" => commands::handle_whoami(args),
+            "leetcode-profile" => commands::handle_profile(args),
             "leetcode-list" => commands::handle_list(args),
             "leetcode-show" => commands::handle_show(args),
+            "leetcode-daily" => commands::handle_daily(args),
+            "leetcode-random" => commands::handle_random(args),
+            "leetcode-sync" => commands::handle_sync(args),
             "leetcode-test" => commands::handle_test(args, worktree),
             "leetcode-submit" => commands::handle_submit(args, worktree),
             _ => Err(format!("Unknown command: {}", command.name)),
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+/// Problems per page when the caller doesn't set a limit
+pub const DEFAULT_PAGE_SIZE: i32 = 50;
+
 /// Difficulty levels for LeetCode problems
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
@@ -19,6 +22,80 @@ impl std::fmt::Display for Difficulty {
     }
 }
 
+/// LeetCode site (region) the extension talks to
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Site {
+    #[default]
+    #[serde(rename = "leetcode.com")]
+    Global,
+    #[serde(rename = "leetcode.cn")]
+    China,
+}
+
+impl Site {
+    /// Base URL of the site without trailing slash
+    pub fn base_url(&self) -> &'static str {
+        match self {
+            Site::Global => "https://leetcode.com",
+            Site::China => "https://leetcode.cn",
+        }
+    }
+
+    /// GraphQL endpoint for the site
+    pub fn graphql_url(&self) -> String {
+        format!("{}/graphql/", self.base_url())
+    }
+
+    /// Name of the session cookie set by the site
+    pub fn session_cookie_name(&self) -> &'static str {
+        match self {
+            Site::Global | Site::China => "LEETCODE_SESSION",
+        }
+    }
+
+    /// Name of the CSRF cookie set by the site
+    pub fn csrf_cookie_name(&self) -> &'static str {
+        match self {
+            Site::Global | Site::China => "csrftoken",
+        }
+    }
+
+    /// Whether `problemsetQuestionList` accepts the `orderBy`/`sortOrder` filters
+    pub fn supports_list_sorting(&self) -> bool {
+        matches!(self, Site::Global)
+    }
+
+    /// Build a Cookie header value, accepting either a bare session value or `name=value` pairs
+    pub fn cookie_header(&self, session_cookie: &str) -> String {
+        if session_cookie.contains('=') {
+            session_cookie.to_string()
+        } else {
+            format!("{}={}", self.session_cookie_name(), session_cookie)
+        }
+    }
+}
+
+impl std::fmt::Display for Site {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Site::Global => write!(f, "leetcode.com"),
+            Site::China => write!(f, "leetcode.cn"),
+        }
+    }
+}
+
+impl std::str::FromStr for Site {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "com" | "leetcode.com" | "global" | "us" => Ok(Site::Global),
+            "cn" | "leetcode.cn" | "china" => Ok(Site::China),
+            _ => Err(format!("Invalid site: {}. Use com|cn", s)),
+        }
+    }
+}
+
 /// Problem tag information
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Tag {
@@ -43,6 +120,31 @@ pub struct Problem {
     pub is_paid_only: bool,
     #[serde(rename = "acRate")]
     pub acceptance_rate: f64,
+    /// Localized title (leetcode.cn only)
+    #[serde(rename = "translatedTitle", default, skip_serializing_if = "Option::is_none")]
+    pub translated_title: Option<String>,
+    /// Signed-in user's progress: "ac"/"notac" on leetcode.com, "AC"/"TRIED" on leetcode.cn
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+}
+
+impl Problem {
+    /// Signed-in user's progress on this problem
+    pub fn solve_status(&self) -> SolveStatus {
+        match self.status.as_deref().map(str::to_lowercase).as_deref() {
+            Some("ac") => SolveStatus::Solved,
+            Some("notac") | Some("tried") => SolveStatus::Attempted,
+            _ => SolveStatus::NotStarted,
+        }
+    }
+}
+
+/// User progress on a problem as reported by problem listings
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SolveStatus {
+    Solved,
+    Attempted,
+    NotStarted,
 }
 
 /// Problem details with content
@@ -65,6 +167,12 @@ pub struct ProblemDetail {
     pub sample_test_case: String,
     #[serde(rename = "exampleTestcases")]
     pub example_testcases: Option<String>,
+    /// Localized title (leetcode.cn only)
+    #[serde(rename = "translatedTitle", default, skip_serializing_if = "Option::is_none")]
+    pub translated_title: Option<String>,
+    /// Localized HTML content (leetcode.cn only)
+    #[serde(rename = "translatedContent", default, skip_serializing_if = "Option::is_none")]
+    pub translated_content: Option<String>,
 }
 
 /// Code snippet for different languages
@@ -76,18 +184,127 @@ pub struct CodeSnippet {
     pub code: String,
 }
 
+/// Field /leetcode-list results can be sorted by
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SortField {
+    Id,
+    Acceptance,
+    Difficulty,
+    Frequency,
+}
+
+impl SortField {
+    /// Value of the GraphQL `orderBy` filter
+    pub fn order_by(&self) -> &'static str {
+        match self {
+            SortField::Id => "FRONTEND_ID",
+            SortField::Acceptance => "AC_RATE",
+            SortField::Difficulty => "DIFFICULTY",
+            SortField::Frequency => "FREQUENCY",
+        }
+    }
+}
+
+impl std::fmt::Display for SortField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SortField::Id => write!(f, "id"),
+            SortField::Acceptance => write!(f, "acceptance"),
+            SortField::Difficulty => write!(f, "difficulty"),
+            SortField::Frequency => write!(f, "frequency"),
+        }
+    }
+}
+
+impl std::str::FromStr for SortField {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "id" => Ok(SortField::Id),
+            "acceptance" => Ok(SortField::Acceptance),
+            "difficulty" => Ok(SortField::Difficulty),
+            "frequency" => Ok(SortField::Frequency),
+            _ => Err(format!("Invalid sort: {}. Use id|acceptance|difficulty|frequency", s)),
+        }
+    }
+}
+
+/// Direction of a sorted problem list
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SortOrder {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+impl SortOrder {
+    /// Value of the GraphQL `sortOrder` filter
+    pub fn sort_order(&self) -> &'static str {
+        match self {
+            SortOrder::Ascending => "ASCENDING",
+            SortOrder::Descending => "DESCENDING",
+        }
+    }
+}
+
+impl std::fmt::Display for SortOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SortOrder::Ascending => write!(f, "asc"),
+            SortOrder::Descending => write!(f, "desc"),
+        }
+    }
+}
+
+impl std::str::FromStr for SortOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "asc" | "ascending" => Ok(SortOrder::Ascending),
+            "desc" | "descending" => Ok(SortOrder::Descending),
+            _ => Err(format!("Invalid order: {}. Use asc|desc", s)),
+        }
+    }
+}
+
 /// Filters for problem list queries
 #[derive(Debug, Clone, Default)]
 pub struct ProblemFilters {
     pub difficulty: Option<Difficulty>,
     pub tags: Vec<String>,
     pub company_tag: Option<String>,
-    pub status: Option<String>, // "TODO", "SOLVED", etc.
+    pub status: Option<String>, // "NOT_STARTED", "AC" or "TRIED"
+    pub premium_only: Option<bool>,
+    pub search_keywords: Option<String>,
+    pub sort_by: Option<SortField>,
+    pub sort_order: SortOrder,
     pub list_id: Option<String>,
     pub skip: Option<i32>,
     pub limit: Option<i32>,
 }
 
+/// GraphQL request body sent to the LeetCode endpoint
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GraphQLRequest {
+    #[serde(rename = "operationName")]
+    pub operation_name: String,
+    pub query: String,
+    pub variables: serde_json::Value,
+}
+
+impl GraphQLRequest {
+    /// Create a request for a named operation
+    pub fn new(operation_name: &str, query: &str, variables: serde_json::Value) -> Self {
+        Self {
+            operation_name: operation_name.to_string(),
+            query: query.to_string(),
+            variables,
+        }
+    }
+}
+
 /// GraphQL response wrapper
 #[derive(Debug, Deserialize)]
 pub struct GraphQLResponse<T> {
@@ -103,10 +320,27 @@ pub enum TestStatus {
     RuntimeError,
     TimeLimitExceeded,
     MemoryLimitExceeded,
+    OutputLimitExceeded,
     WrongAnswer,
     UnknownError,
 }
 
+impl TestStatus {
+    /// Map a judge status code; see `SubmissionStatus::from_status_code`
+    pub fn from_status_code(status_code: u32) -> Self {
+        match SubmissionStatus::from_status_code(status_code) {
+            SubmissionStatus::Accepted => TestStatus::Success,
+            SubmissionStatus::WrongAnswer => TestStatus::WrongAnswer,
+            SubmissionStatus::MemoryLimitExceeded => TestStatus::MemoryLimitExceeded,
+            SubmissionStatus::OutputLimitExceeded => TestStatus::OutputLimitExceeded,
+            SubmissionStatus::TimeLimitExceeded => TestStatus::TimeLimitExceeded,
+            SubmissionStatus::RuntimeError => TestStatus::RuntimeError,
+            SubmissionStatus::CompileError => TestStatus::CompileError,
+            _ => TestStatus::UnknownError,
+        }
+    }
+}
+
 impl std::fmt::Display for TestStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -115,6 +349,7 @@ impl std::fmt::Display for TestStatus {
             TestStatus::RuntimeError => write!(f, "❌ Runtime Error"),
             TestStatus::TimeLimitExceeded => write!(f, "⏰ Time Limit Exceeded"),
             TestStatus::MemoryLimitExceeded => write!(f, "💾 Memory Limit Exceeded"),
+            TestStatus::OutputLimitExceeded => write!(f, "📄 Output Limit Exceeded"),
             TestStatus::WrongAnswer => write!(f, "❌ Wrong Answer"),
             TestStatus::UnknownError => write!(f, "❓ Unknown Error"),
         }
@@ -131,6 +366,22 @@ pub struct TestResult {
     pub total_tests: u32,
     pub failed_test_case: Option<String>,
     pub compile_error: Option<String>,
+    /// Per-testcase breakdown, empty when the judge did not report one
+    #[serde(default)]
+    pub cases: Vec<CaseOutcome>,
+    /// Combined stdout of the run when no per-testcase stdout is available
+    #[serde(default)]
+    pub stdout: Option<String>,
+}
+
+/// Outcome of a single testcase in a test run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CaseOutcome {
+    pub input: String,
+    pub expected: String,
+    pub actual: String,
+    pub stdout: Option<String>,
+    pub passed: bool,
 }
 
 /// Test execution response data structure
@@ -145,6 +396,9 @@ pub struct TestExecutionData {
 pub struct TestSubmissionResult {
     #[serde(rename = "submissionId")]
     pub submission_id: String,
+    /// Judge progress: PENDING, STARTED or SUCCESS
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
     #[serde(rename = "statusCode")]
     pub status_code: u32,
     pub status: String,
@@ -166,6 +420,60 @@ pub struct TestSubmissionResult {
     pub expected_output: Option<String>,
     #[serde(rename = "codeOutput")]
     pub code_output: Option<String>,
+    #[serde(rename = "stdOutput", default)]
+    pub std_output: Option<String>,
+}
+
+/// Response of `POST /problems/{slug}/interpret_solution/`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InterpretResponse {
+    pub interpret_id: String,
+    #[serde(default)]
+    pub test_case: Option<String>,
+}
+
+/// Response of `POST /problems/{slug}/submit/`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubmitResponse {
+    pub submission_id: u64,
+}
+
+/// Response of `GET /submissions/detail/{id}/check/` for both test runs and submissions
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CheckResponse {
+    /// `PENDING`, `STARTED` or `SUCCESS`
+    pub state: String,
+    pub status_code: Option<u32>,
+    pub status_msg: Option<String>,
+    pub run_success: Option<bool>,
+    pub correct_answer: Option<bool>,
+    pub status_runtime: Option<String>,
+    pub status_memory: Option<String>,
+    pub runtime_percentile: Option<f64>,
+    pub memory_percentile: Option<f64>,
+    pub total_correct: Option<u32>,
+    pub total_testcases: Option<u32>,
+    pub code_answer: Vec<String>,
+    pub expected_code_answer: Vec<String>,
+    /// Per-testcase pass flags such as `"101"` (test runs only)
+    pub compare_result: Option<String>,
+    pub std_output: Option<String>,
+    pub std_output_list: Vec<String>,
+    pub compile_error: Option<String>,
+    pub full_compile_error: Option<String>,
+    pub runtime_error: Option<String>,
+    pub full_runtime_error: Option<String>,
+    pub last_testcase: Option<String>,
+    pub expected_output: Option<String>,
+    pub code_output: Option<String>,
+}
+
+impl CheckResponse {
+    /// Whether the judge has finished processing
+    pub fn is_complete(&self) -> bool {
+        self.state == "SUCCESS"
+    }
 }
 
 /// Generic GraphQL error structure
@@ -188,12 +496,328 @@ pub struct ProblemSetQuestionList {
     pub questions: Vec<Problem>,
 }
 
+/// One page of `/leetcode-list` results and where it sits in the full result set
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProblemPage {
+    /// Number of problems matching the filters across all pages
+    pub total: i32,
+    pub skip: i32,
+    pub limit: i32,
+    pub questions: Vec<Problem>,
+}
+
+impl ProblemPage {
+    /// 1-based page number; pages not aligned to `limit` round down
+    pub fn page_number(&self) -> i32 {
+        self.skip / self.limit.max(1) + 1
+    }
+
+    /// Number of pages needed to show all `total` problems
+    pub fn page_count(&self) -> i32 {
+        ((self.total + self.limit.max(1) - 1) / self.limit.max(1)).max(1)
+    }
+
+    /// Skip of the following page, if there is one
+    pub fn next_skip(&self) -> Option<i32> {
+        let next = self.skip.saturating_add(self.limit.max(1));
+        (next < self.total).then_some(next)
+    }
+
+    /// Skip of the preceding page, if there is one
+    pub fn previous_skip(&self) -> Option<i32> {
+        (self.skip > 0).then(|| (self.skip - self.limit).max(0))
+    }
+}
+
+/// Minimal problem identity used to resolve IDs and titles to slugs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProblemIndexEntry {
+    pub frontend_id: String,
+    pub title: String,
+    pub title_slug: String,
+}
+
+impl From<&Problem> for ProblemIndexEntry {
+    fn from(problem: &Problem) -> Self {
+        Self {
+            frontend_id: problem.frontend_id.clone(),
+            title: problem.title.clone(),
+            title_slug: problem.title_slug.clone(),
+        }
+    }
+}
+
+/// Frontend ID / title → slug index built from `problemsetQuestionList`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProblemIndex {
+    pub entries: Vec<ProblemIndexEntry>,
+    pub updated_at: String,
+}
+
+impl ProblemIndex {
+    /// Create an index stamped with the current time
+    pub fn new(entries: Vec<ProblemIndexEntry>) -> Self {
+        Self {
+            entries,
+            updated_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Find an entry by its frontend ID (the number shown on the website)
+    pub fn find_by_frontend_id(&self, frontend_id: &str) -> Option<&ProblemIndexEntry> {
+        self.entries.iter().find(|entry| entry.frontend_id == frontend_id)
+    }
+
+    /// Find an entry by exact title, ignoring case
+    pub fn find_by_title(&self, title: &str) -> Option<&ProblemIndexEntry> {
+        let title = title.trim();
+        self.entries.iter().find(|entry| entry.title.eq_ignore_ascii_case(title))
+    }
+
+    /// Find an entry by title slug
+    pub fn find_by_slug(&self, title_slug: &str) -> Option<&ProblemIndexEntry> {
+        self.entries.iter().find(|entry| entry.title_slug == title_slug)
+    }
+
+    /// Fuzzy search by title, best matches first
+    ///
+    /// Exact substring matches rank above titles containing every word,
+    /// which rank above titles containing the keywords' letters in order.
+    pub fn search(&self, keywords: &str) -> Vec<&ProblemIndexEntry> {
+        let query = keywords.trim().to_lowercase();
+        if query.is_empty() {
+            return Vec::new();
+        }
+
+        let mut matches: Vec<(u8, &ProblemIndexEntry)> = self.entries.iter()
+            .filter_map(|entry| match_rank(&entry.title.to_lowercase(), &query).map(|rank| (rank, entry)))
+            .collect();
+        matches.sort_by_key(|(rank, entry)| (*rank, entry.frontend_id.parse::<u32>().unwrap_or(u32::MAX)));
+        matches.into_iter().map(|(_, entry)| entry).collect()
+    }
+}
+
+/// Full problem catalog synced by /leetcode-sync for offline listing and search
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProblemCatalog {
+    pub site: Site,
+    pub synced_at: String,
+    pub problems: Vec<Problem>,
+}
+
+impl ProblemCatalog {
+    /// Create a catalog stamped with the current time
+    pub fn new(site: Site, problems: Vec<Problem>) -> Self {
+        Self {
+            site,
+            synced_at: chrono::Utc::now().to_rfc3339(),
+            problems,
+        }
+    }
+
+    /// Slug index derived from the catalog
+    pub fn to_index(&self) -> ProblemIndex {
+        ProblemIndex::new(self.problems.iter().map(ProblemIndexEntry::from).collect())
+    }
+
+    /// Whether `filters` can be answered from the catalog
+    ///
+    /// Company tags and favorite lists are not part of `Problem`.
+    pub fn supports(filters: &ProblemFilters) -> bool {
+        filters.company_tag.is_none() && filters.list_id.is_none()
+    }
+
+    /// Apply `filters` locally, mirroring what `problemsetQuestionList` returns
+    pub fn query(&self, filters: &ProblemFilters) -> ProblemPage {
+        let query = filters.search_keywords.as_deref().map(|keywords| keywords.trim().to_lowercase());
+        let status = filters.status.as_deref().map(|status| match status {
+            "AC" => SolveStatus::Solved,
+            "TRIED" => SolveStatus::Attempted,
+            _ => SolveStatus::NotStarted,
+        });
+
+        let mut matches: Vec<(u8, Problem)> = self.problems.iter()
+            .filter(|problem| filters.difficulty.as_ref().is_none_or(|difficulty| &problem.difficulty == difficulty))
+            .filter(|problem| filters.tags.iter().all(|tag| {
+                problem.tags.iter().any(|t| t.slug.eq_ignore_ascii_case(tag) || t.name.eq_ignore_ascii_case(tag))
+            }))
+            .filter(|problem| status.is_none_or(|status| problem.solve_status() == status))
+            .filter(|problem| filters.premium_only.is_none_or(|premium_only| problem.is_paid_only == premium_only))
+            .filter_map(|problem| match query {
+                Some(ref query) => match_rank(&problem.title.to_lowercase(), query).map(|rank| (rank, problem.clone())),
+                None => Some((0, problem.clone())),
+            })
+            .collect();
+        // Best search matches first; stable, so ties keep catalog (ID) order
+        matches.sort_by_key(|(rank, _)| *rank);
+
+        let mut problems: Vec<Problem> = matches.into_iter().map(|(_, problem)| problem).collect();
+        if let Some(field) = filters.sort_by {
+            sort_problems(&mut problems, field, filters.sort_order);
+        }
+
+        let total = problems.len() as i32;
+        let skip = filters.skip.unwrap_or(0);
+        let limit = filters.limit.unwrap_or(DEFAULT_PAGE_SIZE);
+        let questions = problems.into_iter().skip(skip as usize).take(limit as usize).collect();
+
+        ProblemPage { total, skip, limit, questions }
+    }
+}
+
+/// Sort problems in place by `field`
+///
+/// Frequency is not part of `Problem`, so sorting by it keeps the current order.
+pub fn sort_problems(problems: &mut [Problem], field: SortField, order: SortOrder) {
+    let difficulty_rank = |difficulty: &Difficulty| match difficulty {
+        Difficulty::Easy => 0,
+        Difficulty::Medium => 1,
+        Difficulty::Hard => 2,
+    };
+
+    problems.sort_by(|a, b| {
+        let ordering = match field {
+            SortField::Id => a.frontend_id.parse::<u32>().unwrap_or(u32::MAX)
+                .cmp(&b.frontend_id.parse::<u32>().unwrap_or(u32::MAX)),
+            SortField::Acceptance => a.acceptance_rate.total_cmp(&b.acceptance_rate),
+            SortField::Difficulty => difficulty_rank(&a.difficulty).cmp(&difficulty_rank(&b.difficulty)),
+            SortField::Frequency => std::cmp::Ordering::Equal,
+        };
+        match order {
+            SortOrder::Ascending => ordering,
+            SortOrder::Descending => ordering.reverse(),
+        }
+    });
+}
+
+/// Rank how well a lowercase title matches a lowercase query; lower is better
+fn match_rank(title: &str, query: &str) -> Option<u8> {
+    if title.contains(query) {
+        return Some(0);
+    }
+    if query.split_whitespace().all(|word| title.contains(word)) {
+        return Some(1);
+    }
+
+    let mut title_chars = title.chars();
+    let is_subsequence = query.chars()
+        .filter(|c| !c.is_whitespace())
+        .all(|c| title_chars.any(|t| t == c));
+    is_subsequence.then_some(2)
+}
+
 /// Response structure for problem detail query  
 #[derive(Debug, Deserialize)]
 pub struct QuestionData {
     pub question: Option<ProblemDetail>,
 }
 
+/// Reference to a question by slug, as returned by the daily and random queries
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuestionSlug {
+    #[serde(rename = "titleSlug")]
+    pub title_slug: String,
+}
+
+/// Today's coding challenge and the user's progress on it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyChallenge {
+    pub date: String,
+    /// "Finish"/"NotStart" on leetcode.com, "FINISH"/"NOT_START" on leetcode.cn
+    #[serde(rename = "userStatus", default)]
+    pub user_status: Option<String>,
+    #[serde(default)]
+    pub link: Option<String>,
+    pub question: QuestionSlug,
+}
+
+impl DailyChallenge {
+    /// Whether the signed-in user has already solved today's challenge
+    pub fn is_completed(&self) -> bool {
+        self.user_status.as_deref()
+            .is_some_and(|status| status.eq_ignore_ascii_case("finish"))
+    }
+}
+
+/// Response structure for the daily challenge query
+///
+/// leetcode.com answers with `activeDailyCodingChallengeQuestion`,
+/// leetcode.cn with a one-element `todayRecord` list.
+#[derive(Debug, Deserialize)]
+pub struct DailyChallengeData {
+    #[serde(rename = "activeDailyCodingChallengeQuestion", default)]
+    pub active_daily_coding_challenge_question: Option<DailyChallenge>,
+    #[serde(rename = "todayRecord", default)]
+    pub today_record: Vec<DailyChallenge>,
+}
+
+impl DailyChallengeData {
+    /// Today's challenge regardless of which site answered
+    pub fn into_challenge(self) -> Option<DailyChallenge> {
+        self.active_daily_coding_challenge_question
+            .or_else(|| self.today_record.into_iter().next())
+    }
+}
+
+/// Response structure for the random question query
+///
+/// leetcode.com returns a question object, leetcode.cn a bare slug.
+#[derive(Debug, Deserialize)]
+pub struct RandomQuestionData {
+    #[serde(rename = "randomQuestion", default)]
+    pub random_question: Option<QuestionSlug>,
+    #[serde(rename = "problemsetRandomFilteredQuestion", default)]
+    pub problemset_random_filtered_question: Option<String>,
+}
+
+impl RandomQuestionData {
+    /// Slug of the picked question, if any matched the filters
+    pub fn into_title_slug(self) -> Option<String> {
+        self.random_question
+            .map(|question| question.title_slug)
+            .or(self.problemset_random_filtered_question)
+    }
+}
+
+/// Signed-in state and account of a session, from the `globalData` query
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct UserStatus {
+    #[serde(rename = "isSignedIn", default)]
+    pub is_signed_in: bool,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(rename = "isPremium", default)]
+    pub is_premium: Option<bool>,
+    #[serde(default)]
+    pub avatar: Option<String>,
+    #[serde(rename = "realName", default)]
+    pub real_name: Option<String>,
+}
+
+/// Response structure for the `globalData` query
+#[derive(Debug, Deserialize)]
+pub struct GlobalData {
+    #[serde(rename = "userStatus")]
+    pub user_status: UserStatus,
+}
+
+/// Easy, free problem `id` for tests; adjust fields with struct update syntax
+#[cfg(test)]
+pub(crate) fn test_problem(id: &str) -> Problem {
+    Problem {
+        id: id.to_string(),
+        frontend_id: id.to_string(),
+        title: format!("Problem {}", id),
+        title_slug: format!("problem-{}", id),
+        difficulty: Difficulty::Easy,
+        tags: vec![],
+        is_paid_only: false,
+        acceptance_rate: 0.5,
+        translated_title: None,
+        status: None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -224,6 +848,247 @@ mod tests {
         assert!(filters.status.is_none());
     }
 
+    #[test]
+    fn test_site_urls_and_parsing() {
+        assert_eq!(Site::default(), Site::Global);
+        assert_eq!(Site::Global.graphql_url(), "https://leetcode.com/graphql/");
+        assert_eq!(Site::China.graphql_url(), "https://leetcode.cn/graphql/");
+        assert_eq!("cn".parse::<Site>().unwrap(), Site::China);
+        assert_eq!("leetcode.com".parse::<Site>().unwrap(), Site::Global);
+        assert!("jp".parse::<Site>().is_err());
+
+        assert_eq!(serde_json::to_string(&Site::China).unwrap(), "\"leetcode.cn\"");
+    }
+
+    #[test]
+    fn test_site_cookie_header() {
+        assert_eq!(Site::Global.cookie_header("abc"), "LEETCODE_SESSION=abc");
+        assert_eq!(Site::China.cookie_header("LEETCODE_SESSION=abc; csrftoken=x"), "LEETCODE_SESSION=abc; csrftoken=x");
+    }
+
+    #[test]
+    fn test_problem_translated_title_optional() {
+        let json = r#"{
+            "questionId": "1", "questionFrontendId": "1", "title": "Two Sum",
+            "titleSlug": "two-sum", "difficulty": "EASY", "topicTags": [],
+            "isPaidOnly": false, "acRate": 0.5, "translatedTitle": "两数之和"
+        }"#;
+        let problem: Problem = serde_json::from_str(json).unwrap();
+        assert_eq!(problem.translated_title.as_deref(), Some("两数之和"));
+
+        let without: Problem = serde_json::from_str(&json.replace(r#", "translatedTitle": "两数之和""#, "")).unwrap();
+        assert!(without.translated_title.is_none());
+    }
+
+    #[test]
+    fn test_problem_solve_status() {
+        let json = r#"{
+            "questionId": "1", "questionFrontendId": "1", "title": "Two Sum",
+            "titleSlug": "two-sum", "difficulty": "EASY", "topicTags": [],
+            "isPaidOnly": false, "acRate": 0.5, "status": "ac"
+        }"#;
+        let mut problem: Problem = serde_json::from_str(json).unwrap();
+        assert_eq!(problem.solve_status(), SolveStatus::Solved);
+
+        problem.status = Some("notac".to_string());
+        assert_eq!(problem.solve_status(), SolveStatus::Attempted);
+        problem.status = Some("TRIED".to_string());
+        assert_eq!(problem.solve_status(), SolveStatus::Attempted);
+        problem.status = None;
+        assert_eq!(problem.solve_status(), SolveStatus::NotStarted);
+    }
+
+    #[test]
+    fn test_check_response_pending() {
+        let check: CheckResponse = serde_json::from_str(r#"{"state": "STARTED"}"#).unwrap();
+        assert!(!check.is_complete());
+        assert!(check.code_answer.is_empty());
+        assert!(check.status_code.is_none());
+    }
+
+    #[test]
+    fn test_check_response_complete() {
+        let json = r#"{
+            "state": "SUCCESS", "status_code": 10, "status_msg": "Accepted", "run_success": true,
+            "correct_answer": true, "status_runtime": "0 ms", "code_answer": ["[0,1]"],
+            "expected_code_answer": ["[0,1]"], "compare_result": "1", "std_output": "",
+            "std_output_list": ["", ""], "total_correct": 1, "total_testcases": 1
+        }"#;
+        let check: CheckResponse = serde_json::from_str(json).unwrap();
+        assert!(check.is_complete());
+        assert_eq!(check.status_code, Some(10));
+        assert_eq!(check.code_answer, vec!["[0,1]".to_string()]);
+        assert_eq!(check.compare_result.as_deref(), Some("1"));
+        assert_eq!(check.std_output_list.len(), 2);
+    }
+
+    #[test]
+    fn test_graphql_request_serialization() {
+        let request = GraphQLRequest::new(
+            "questionData",
+            "query questionData($titleSlug: String!) { question(titleSlug: $titleSlug) { title } }",
+            serde_json::json!({ "titleSlug": "two-sum" }),
+        );
+
+        let json: serde_json::Value = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["operationName"], "questionData");
+        assert_eq!(json["variables"]["titleSlug"], "two-sum");
+        assert!(json["query"].as_str().unwrap().starts_with("query questionData"));
+    }
+
+    #[test]
+    fn test_problem_index_lookup() {
+        let index = ProblemIndex::new(vec![
+            ProblemIndexEntry {
+                frontend_id: "1".to_string(),
+                title: "Two Sum".to_string(),
+                title_slug: "two-sum".to_string(),
+            },
+            ProblemIndexEntry {
+                frontend_id: "50".to_string(),
+                title: "Pow(x, n)".to_string(),
+                title_slug: "powx-n".to_string(),
+            },
+        ]);
+
+        assert_eq!(index.find_by_frontend_id("50").unwrap().title_slug, "powx-n");
+        assert_eq!(index.find_by_title("two sum").unwrap().frontend_id, "1");
+        assert_eq!(index.find_by_slug("two-sum").unwrap().title, "Two Sum");
+        assert!(index.find_by_frontend_id("2").is_none());
+    }
+
+    #[test]
+    fn test_sort_options_from_str() {
+        assert_eq!("acceptance".parse::<SortField>().unwrap(), SortField::Acceptance);
+        assert_eq!("ID".parse::<SortField>().unwrap().order_by(), "FRONTEND_ID");
+        assert!("likes".parse::<SortField>().unwrap_err().contains("Invalid sort"));
+
+        assert_eq!("desc".parse::<SortOrder>().unwrap(), SortOrder::Descending);
+        assert_eq!(SortOrder::default().sort_order(), "ASCENDING");
+        assert!("up".parse::<SortOrder>().is_err());
+    }
+
+    #[test]
+    fn test_problem_page_navigation() {
+        let page = ProblemPage { total: 120, skip: 50, limit: 50, questions: vec![] };
+        assert_eq!(page.page_number(), 2);
+        assert_eq!(page.page_count(), 3);
+        assert_eq!(page.next_skip(), Some(100));
+        assert_eq!(page.previous_skip(), Some(0));
+
+        let last = ProblemPage { skip: 100, ..page.clone() };
+        assert_eq!(last.page_number(), 3);
+        assert_eq!(last.next_skip(), None);
+
+        let first = ProblemPage { skip: 0, ..page.clone() };
+        assert_eq!(first.previous_skip(), None);
+
+        let empty = ProblemPage { total: 0, skip: 0, limit: 50, questions: vec![] };
+        assert_eq!(empty.page_count(), 1);
+        assert_eq!(empty.next_skip(), None);
+
+        // A zero limit still moves forward instead of linking the same page
+        let zero_limit = ProblemPage { total: 120, skip: 50, limit: 0, questions: vec![] };
+        assert_eq!(zero_limit.next_skip(), Some(51));
+    }
+
+    #[test]
+    fn test_sort_problems_locally() {
+        let problem = |id: &str, difficulty: Difficulty, acceptance_rate: f64| Problem {
+            difficulty,
+            acceptance_rate,
+            ..test_problem(id)
+        };
+        let mut problems = vec![
+            problem("10", Difficulty::Hard, 0.3),
+            problem("2", Difficulty::Easy, 0.6),
+            problem("5", Difficulty::Medium, 0.4),
+        ];
+        let ids = |problems: &[Problem]| problems.iter().map(|p| p.frontend_id.clone()).collect::<Vec<_>>();
+
+        sort_problems(&mut problems, SortField::Id, SortOrder::Ascending);
+        assert_eq!(ids(&problems), vec!["2", "5", "10"]);
+
+        sort_problems(&mut problems, SortField::Acceptance, SortOrder::Descending);
+        assert_eq!(ids(&problems), vec!["2", "5", "10"]);
+
+        sort_problems(&mut problems, SortField::Difficulty, SortOrder::Descending);
+        assert_eq!(ids(&problems), vec!["10", "5", "2"]);
+    }
+
+    #[test]
+    fn test_problem_catalog_query() {
+        let problem = |id: &str, title: &str, difficulty: Difficulty, tag: &str, status: Option<&str>, paid: bool| Problem {
+            title: title.to_string(),
+            title_slug: title.to_lowercase().replace(' ', "-"),
+            difficulty,
+            tags: vec![Tag { name: tag.to_string(), slug: tag.to_lowercase() }],
+            is_paid_only: paid,
+            status: status.map(str::to_string),
+            ..test_problem(id)
+        };
+        let catalog = ProblemCatalog::new(Site::Global, vec![
+            problem("1", "Two Sum", Difficulty::Easy, "Array", Some("ac"), false),
+            problem("146", "LRU Cache", Difficulty::Medium, "Design", None, false),
+            problem("156", "Binary Tree Upside Down", Difficulty::Medium, "Tree", None, true),
+            problem("460", "LFU Cache", Difficulty::Hard, "Design", Some("notac"), false),
+        ]);
+        let ids = |page: ProblemPage| page.questions.into_iter().map(|p| p.frontend_id).collect::<Vec<_>>();
+
+        let filters = ProblemFilters { difficulty: Some(Difficulty::Medium), ..Default::default() };
+        assert_eq!(ids(catalog.query(&filters)), vec!["146", "156"]);
+
+        let filters = ProblemFilters { tags: vec!["design".to_string()], status: Some("TRIED".to_string()), ..Default::default() };
+        assert_eq!(ids(catalog.query(&filters)), vec!["460"]);
+
+        let filters = ProblemFilters {
+            status: Some("NOT_STARTED".to_string()),
+            premium_only: Some(false),
+            ..Default::default()
+        };
+        assert_eq!(ids(catalog.query(&filters)), vec!["146"]);
+
+        let filters = ProblemFilters { search_keywords: Some("cache".to_string()), ..Default::default() };
+        assert_eq!(ids(catalog.query(&filters)), vec!["146", "460"]);
+
+        let filters = ProblemFilters { skip: Some(1), limit: Some(2), ..Default::default() };
+        let page = catalog.query(&filters);
+        assert_eq!(page.total, 4);
+        assert_eq!(ids(page), vec!["146", "156"]);
+
+        assert_eq!(catalog.to_index().find_by_frontend_id("146").unwrap().title_slug, "lru-cache");
+        assert!(!ProblemCatalog::supports(&ProblemFilters { company_tag: Some("google".to_string()), ..Default::default() }));
+    }
+
+    #[test]
+    fn test_problem_index_search() {
+        let index = ProblemIndex::new(vec![
+            ProblemIndexEntry {
+                frontend_id: "460".to_string(),
+                title: "LFU Cache".to_string(),
+                title_slug: "lfu-cache".to_string(),
+            },
+            ProblemIndexEntry {
+                frontend_id: "146".to_string(),
+                title: "LRU Cache".to_string(),
+                title_slug: "lru-cache".to_string(),
+            },
+            ProblemIndexEntry {
+                frontend_id: "1".to_string(),
+                title: "Two Sum".to_string(),
+                title_slug: "two-sum".to_string(),
+            },
+        ]);
+
+        let slugs = |keywords: &str| index.search(keywords).iter().map(|entry| entry.title_slug.as_str()).collect::<Vec<_>>();
+        assert_eq!(slugs("lru cache"), vec!["lru-cache"]);
+        assert_eq!(slugs("cache"), vec!["lru-cache", "lfu-cache"]);
+        assert_eq!(slugs("cache lru"), vec!["lru-cache"]);
+        assert_eq!(slugs("twosum"), vec!["two-sum"]);
+        assert!(slugs("  ").is_empty());
+        assert!(slugs("graph").is_empty());
+    }
+
     #[test]
     fn test_graphql_response_success() {
         let json = r#"{"data": {"test": "value"}}"#;
@@ -244,6 +1109,37 @@ mod tests {
         assert_eq!(errors[0].message, "Test error");
     }
 
+    #[test]
+    fn test_daily_challenge_data_both_sites() {
+        let global = r#"{"activeDailyCodingChallengeQuestion": {"date": "2024-01-15", "userStatus": "Finish", "link": "/problems/two-sum/", "question": {"titleSlug": "two-sum"}}}"#;
+        let data: DailyChallengeData = serde_json::from_str(global).unwrap();
+        let challenge = data.into_challenge().unwrap();
+        assert_eq!(challenge.question.title_slug, "two-sum");
+        assert!(challenge.is_completed());
+
+        let china = r#"{"todayRecord": [{"date": "2024-01-15", "userStatus": "NOT_START", "question": {"titleSlug": "add-two-numbers"}}]}"#;
+        let data: DailyChallengeData = serde_json::from_str(china).unwrap();
+        let challenge = data.into_challenge().unwrap();
+        assert_eq!(challenge.question.title_slug, "add-two-numbers");
+        assert!(challenge.link.is_none());
+        assert!(!challenge.is_completed());
+
+        let empty: DailyChallengeData = serde_json::from_str(r#"{"todayRecord": []}"#).unwrap();
+        assert!(empty.into_challenge().is_none());
+    }
+
+    #[test]
+    fn test_random_question_data_both_sites() {
+        let global: RandomQuestionData = serde_json::from_str(r#"{"randomQuestion": {"titleSlug": "two-sum"}}"#).unwrap();
+        assert_eq!(global.into_title_slug().as_deref(), Some("two-sum"));
+
+        let china: RandomQuestionData = serde_json::from_str(r#"{"problemsetRandomFilteredQuestion": "two-sum"}"#).unwrap();
+        assert_eq!(china.into_title_slug().as_deref(), Some("two-sum"));
+
+        let none: RandomQuestionData = serde_json::from_str(r#"{"randomQuestion": null}"#).unwrap();
+        assert!(none.into_title_slug().is_none());
+    }
+
     #[test]
     fn test_test_result_display() {
         let result = TestResult {
@@ -254,6 +1150,8 @@ mod tests {
             total_tests: 5,
             failed_test_case: None,
             compile_error: None,
+            cases: vec![],
+            stdout: None,
         };
 
         assert_eq!(result.status, TestStatus::Success);
@@ -271,6 +1169,8 @@ mod tests {
             total_tests: 5,
             failed_test_case: Some("Input: [1,2,3]\nExpected: [1,3]\nActual: [1,2]".to_string()),
             compile_error: None,
+            cases: vec![],
+            stdout: None,
         };
 
         assert_eq!(result.status, TestStatus::RuntimeError);
@@ -286,6 +1186,17 @@ mod tests {
         assert_eq!(status2.to_string(), "❌ Wrong Answer");
     }
 
+    #[test]
+    fn test_status_from_code() {
+        assert_eq!(SubmissionStatus::from_status_code(13), SubmissionStatus::OutputLimitExceeded);
+        assert_eq!(SubmissionStatus::from_status_code(14), SubmissionStatus::TimeLimitExceeded);
+        assert_eq!(SubmissionStatus::from_status_code(20), SubmissionStatus::CompileError);
+        assert_eq!(TestStatus::from_status_code(10), TestStatus::Success);
+        assert_eq!(TestStatus::from_status_code(13), TestStatus::OutputLimitExceeded);
+        assert_eq!(TestStatus::from_status_code(15), TestStatus::RuntimeError);
+        assert_eq!(TestStatus::from_status_code(16), TestStatus::UnknownError);
+    }
+
     #[test]
     fn test_submission_result_success() {
         let result = SubmissionResult {
@@ -322,6 +1233,26 @@ pub enum SubmissionStatus {
     Unknown,
 }
 
+impl SubmissionStatus {
+    /// Map a judge status code
+    ///
+    /// 10 accepted, 11 wrong answer, 12 MLE, 13 output limit, 14 TLE,
+    /// 15 runtime error, 16 internal error, 20 compile error.
+    pub fn from_status_code(status_code: u32) -> Self {
+        match status_code {
+            10 => SubmissionStatus::Accepted,
+            11 => SubmissionStatus::WrongAnswer,
+            12 => SubmissionStatus::MemoryLimitExceeded,
+            13 => SubmissionStatus::OutputLimitExceeded,
+            14 => SubmissionStatus::TimeLimitExceeded,
+            15 => SubmissionStatus::RuntimeError,
+            16 => SubmissionStatus::InternalError,
+            20 => SubmissionStatus::CompileError,
+            _ => SubmissionStatus::Unknown,
+        }
+    }
+}
+
 impl std::fmt::Display for SubmissionStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -380,6 +1311,9 @@ pub struct SubmissionCheckData {
 pub struct SubmissionDetails {
     #[serde(rename = "submissionId")]
     pub submission_id: String,
+    /// Judge progress: PENDING, STARTED or SUCCESS
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
     #[serde(rename = "statusCode")]
     pub status_code: u32,
     pub status: String,
@@ -0,0 +1,98 @@
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+/// Client-side token bucket limiting how fast `LeetCodeApi` sends requests
+///
+/// Holds up to `capacity` tokens refilled at `refill_per_sec`; each request
+/// takes one token and waits for the next refill when the bucket is empty.
+#[derive(Debug)]
+pub struct TokenBucket {
+    capacity: f64,
+    refill_per_sec: f64,
+    tokens: Cell<f64>,
+    last_refill: Cell<Instant>,
+}
+
+impl TokenBucket {
+    /// Create a full bucket
+    pub fn new(capacity: u32, refill_per_sec: f64) -> Self {
+        Self {
+            capacity: capacity as f64,
+            refill_per_sec,
+            tokens: Cell::new(capacity as f64),
+            last_refill: Cell::new(Instant::now()),
+        }
+    }
+
+    /// Bucket that never delays requests
+    pub fn unlimited() -> Self {
+        Self::new(u32::MAX, f64::INFINITY)
+    }
+
+    /// Take a token, sleeping until one is available
+    pub fn acquire(&self) {
+        if let Some(wait) = self.reserve(Instant::now()) {
+            std::thread::sleep(wait);
+        }
+    }
+
+    /// Take a token at `now`, returning how long to wait before using it
+    ///
+    /// The balance may go negative so back-to-back callers queue up behind each other.
+    fn reserve(&self, now: Instant) -> Option<Duration> {
+        if self.refill_per_sec.is_infinite() {
+            return None;
+        }
+
+        let elapsed = now.saturating_duration_since(self.last_refill.get()).as_secs_f64();
+        let tokens = (self.tokens.get() + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill.set(now);
+        self.tokens.set(tokens - 1.0);
+
+        if tokens >= 1.0 {
+            None
+        } else {
+            Some(Duration::from_secs_f64((1.0 - tokens) / self.refill_per_sec))
+        }
+    }
+}
+
+impl Default for TokenBucket {
+    /// Burst of 10 requests, then 2 per second
+    fn default() -> Self {
+        Self::new(10, 2.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_burst_then_wait() {
+        let bucket = TokenBucket::new(2, 4.0);
+        let start = bucket.last_refill.get();
+
+        assert_eq!(bucket.reserve(start), None);
+        assert_eq!(bucket.reserve(start), None);
+        assert_eq!(bucket.reserve(start), Some(Duration::from_millis(250)));
+        // A second caller queues behind the first
+        assert_eq!(bucket.reserve(start), Some(Duration::from_millis(500)));
+    }
+
+    #[test]
+    fn test_refill_is_capped() {
+        let bucket = TokenBucket::new(1, 1.0);
+        let start = bucket.last_refill.get();
+
+        assert_eq!(bucket.reserve(start + Duration::from_secs(60)), None);
+        assert!(bucket.reserve(start + Duration::from_secs(60)).is_some());
+    }
+
+    #[test]
+    fn test_unlimited_never_waits() {
+        let bucket = TokenBucket::unlimited();
+        let now = Instant::now();
+        assert!((0..1000).all(|_| bucket.reserve(now).is_none()));
+    }
+}
@@ -0,0 +1,122 @@
+use crate::api::LeetCodeApi;
+use crate::file_manager::FileManager;
+use crate::models::ProblemIndex;
+use anyhow::{Context, Result};
+
+/// The different ways a user can refer to a problem
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProblemIdentifier {
+    /// Number shown on the website, e.g. `1`
+    FrontendId(String),
+    /// Title slug, e.g. `two-sum` (also extracted from problem URLs)
+    Slug(String),
+    /// Exact problem title, e.g. `Two Sum`
+    Title(String),
+}
+
+impl ProblemIdentifier {
+    /// Classify user input as a frontend ID, slug (or problem URL) or title
+    pub fn parse(input: &str) -> Self {
+        let input = input.trim();
+
+        if let Some(slug) = Self::slug_from_url(input) {
+            return ProblemIdentifier::Slug(slug);
+        }
+
+        if !input.is_empty() && input.chars().all(|c| c.is_ascii_digit()) {
+            return ProblemIdentifier::FrontendId(input.to_string());
+        }
+
+        let looks_like_slug = !input.is_empty()
+            && input.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+
+        if looks_like_slug {
+            ProblemIdentifier::Slug(input.to_string())
+        } else {
+            ProblemIdentifier::Title(input.to_string())
+        }
+    }
+
+    /// Extract the slug from `https://leetcode.com/problems/<slug>/...`
+    fn slug_from_url(input: &str) -> Option<String> {
+        let (_, rest) = input.split_once("/problems/")?;
+        let slug = rest.split(['/', '?', '#']).next()?;
+
+        if slug.is_empty() {
+            None
+        } else {
+            Some(slug.to_string())
+        }
+    }
+}
+
+/// Resolve a frontend ID, title, slug or problem URL to a title slug
+///
+/// IDs and titles are looked up in the cached index; on a miss the index is
+/// rebuilt from the API once, since new problems are added every week.
+pub fn resolve_title_slug(input: &str, api: &LeetCodeApi, file_manager: &FileManager) -> Result<String> {
+    let identifier = ProblemIdentifier::parse(input);
+
+    if let ProblemIdentifier::Slug(slug) = identifier {
+        return Ok(slug);
+    }
+
+    let cached = file_manager.load_problem_index()?
+        .and_then(|index| lookup(&index, &identifier));
+    if let Some(slug) = cached {
+        return Ok(slug);
+    }
+
+    let index = api.fetch_problem_index()
+        .context("Failed to build problem index")?;
+    file_manager.save_problem_index(&index)?;
+
+    lookup(&index, &identifier)
+        .ok_or_else(|| anyhow::anyhow!("Problem not found: {}", input))
+}
+
+/// Look up an ID or title in the index
+fn lookup(index: &ProblemIndex, identifier: &ProblemIdentifier) -> Option<String> {
+    let entry = match identifier {
+        ProblemIdentifier::FrontendId(id) => index.find_by_frontend_id(id),
+        ProblemIdentifier::Title(title) => index.find_by_title(title),
+        ProblemIdentifier::Slug(slug) => index.find_by_slug(slug),
+    };
+
+    entry.map(|entry| entry.title_slug.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_frontend_id() {
+        assert_eq!(ProblemIdentifier::parse("1"), ProblemIdentifier::FrontendId("1".to_string()));
+        assert_eq!(ProblemIdentifier::parse(" 146 "), ProblemIdentifier::FrontendId("146".to_string()));
+    }
+
+    #[test]
+    fn test_parse_slug() {
+        assert_eq!(ProblemIdentifier::parse("two-sum"), ProblemIdentifier::Slug("two-sum".to_string()));
+        assert_eq!(ProblemIdentifier::parse("3sum"), ProblemIdentifier::Slug("3sum".to_string()));
+    }
+
+    #[test]
+    fn test_parse_title() {
+        assert_eq!(ProblemIdentifier::parse("Two Sum"), ProblemIdentifier::Title("Two Sum".to_string()));
+        assert_eq!(ProblemIdentifier::parse("Pow(x, n)"), ProblemIdentifier::Title("Pow(x, n)".to_string()));
+    }
+
+    #[test]
+    fn test_parse_url() {
+        let expected = ProblemIdentifier::Slug("two-sum".to_string());
+        assert_eq!(ProblemIdentifier::parse("https://leetcode.com/problems/two-sum/"), expected);
+        assert_eq!(ProblemIdentifier::parse("https://leetcode.com/problems/two-sum/description/?envType=daily"), expected);
+        assert_eq!(ProblemIdentifier::parse("leetcode.com/problems/two-sum"), expected);
+        assert_eq!(
+            ProblemIdentifier::parse("https://leetcode.com/problems/"),
+            ProblemIdentifier::Title("https://leetcode.com/problems/".to_string())
+        );
+    }
+}
@@ -34,7 +34,7 @@ impl TemplateGenerator {
         template.push_str(&format!("{} Tags: {}\n", comment_prefix, 
             problem.tags.iter().map(|t| t.name.as_str()).collect::<Vec<_>>().join(", ")));
         template.push_str(&format!("{}\n", separator));
-        template.push_str("\n");
+        template.push('\n');
         
         // Add problem description (truncated)
         let description = Self::extract_problem_description(&problem.content);
@@ -45,19 +45,19 @@ impl TemplateGenerator {
             template.push_str(&format!("{} ...\n", comment_prefix));
         }
         template.push_str(&format!("{}\n", separator));
-        template.push_str("\n");
+        template.push('\n');
         
         // Add example if available
         if !problem.sample_test_case.is_empty() {
             template.push_str(&format!("{} Example:\n", comment_prefix));
             template.push_str(&format!("{} Input: {}\n", comment_prefix, problem.sample_test_case));
             template.push_str(&format!("{}\n", separator));
-            template.push_str("\n");
+            template.push('\n');
         }
         
         // Add the code snippet
         template.push_str(&code_snippet.code);
-        template.push_str("\n");
+        template.push('\n');
         
         template
     }
@@ -177,6 +177,8 @@ mod tests {
             ],
             sample_test_case: "nums = [2,7,11,15], target = 9".to_string(),
             example_testcases: Some("Input: nums = [2,7,11,15], target = 9\nOutput: [0,1]".to_string()),
+            translated_title: None,
+            translated_content: None,
         }
     }
 
@@ -0,0 +1,374 @@
+use anyhow::{Context, Result};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::process::Command;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// Marker appended by curl's `--write-out` so the status code can be split from the body
+const STATUS_MARKER: &str = "\n__HTTP_STATUS__:";
+
+/// HTTP method supported by the transport layer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HttpMethod {
+    Get,
+    Post,
+}
+
+impl HttpMethod {
+    fn as_str(&self) -> &'static str {
+        match self {
+            HttpMethod::Get => "GET",
+            HttpMethod::Post => "POST",
+        }
+    }
+}
+
+/// Outgoing HTTP request
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpRequest {
+    pub method: HttpMethod,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+impl HttpRequest {
+    /// Create a POST request with a body
+    pub fn post(url: &str, body: String) -> Self {
+        Self {
+            method: HttpMethod::Post,
+            url: url.to_string(),
+            headers: Vec::new(),
+            body: Some(body),
+        }
+    }
+
+    /// Create a GET request
+    pub fn get(url: &str) -> Self {
+        Self {
+            method: HttpMethod::Get,
+            url: url.to_string(),
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    /// Add a header to the request
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Look up a header value by name (case-insensitive)
+    pub fn header_value(&self, name: &str) -> Option<&str> {
+        self.headers.iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Response returned by a transport
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl HttpResponse {
+    /// Create a response with the given status code and body
+    pub fn new(status: u16, body: &str) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: body.to_string(),
+        }
+    }
+
+    /// Add a header to the response
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Look up a header value by name (case-insensitive)
+    pub fn header_value(&self, name: &str) -> Option<&str> {
+        self.headers.iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Delay requested by a `Retry-After` header, in seconds or as an HTTP date
+    pub fn retry_after(&self) -> Option<Duration> {
+        let value = self.header_value("Retry-After")?.trim();
+
+        if let Ok(seconds) = value.parse::<u64>() {
+            return Some(Duration::from_secs(seconds));
+        }
+
+        let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+        let seconds = (date.with_timezone(&chrono::Utc) - chrono::Utc::now()).num_seconds();
+        Some(Duration::from_secs(seconds.max(0) as u64))
+    }
+
+    /// Create a 200 OK response
+    pub fn ok(body: &str) -> Self {
+        Self::new(200, body)
+    }
+
+    /// Check whether the status code is in the 2xx range
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+/// Transport used by `LeetCodeApi` to talk to LeetCode
+pub trait HttpTransport {
+    /// Send a request and return the raw response
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse>;
+}
+
+/// Default transport that shells out to the system curl binary
+#[derive(Debug, Clone, Default)]
+pub struct CurlTransport;
+
+impl CurlTransport {
+    /// Build the curl argument list for a request
+    fn build_args(request: &HttpRequest) -> Vec<String> {
+        let mut args = vec![
+            "-s".to_string(),
+            "-X".to_string(),
+            request.method.as_str().to_string(),
+            request.url.clone(),
+            "-w".to_string(),
+            format!("{}%{{http_code}}", STATUS_MARKER),
+            "-i".to_string(),
+        ];
+
+        for (name, value) in &request.headers {
+            args.push("-H".to_string());
+            args.push(format!("{}: {}", name, value));
+        }
+
+        if let Some(ref body) = request.body {
+            args.push("-d".to_string());
+            args.push(body.clone());
+        }
+
+        args
+    }
+
+    /// Split curl output into body and status code
+    fn parse_output(output: &str) -> Result<HttpResponse> {
+        let marker_pos = output.rfind(STATUS_MARKER)
+            .context("Missing HTTP status in curl output")?;
+
+        let status = output[marker_pos + STATUS_MARKER.len()..]
+            .trim()
+            .parse::<u16>()
+            .context("Invalid HTTP status in curl output")?;
+
+        let (headers, body) = Self::split_headers(&output[..marker_pos]);
+        Ok(HttpResponse {
+            status,
+            headers,
+            body: body.to_string(),
+        })
+    }
+
+    /// Strip the header blocks `-i` prepends (one per redirect or `100 Continue`)
+    /// and return the headers of the final response along with the body
+    fn split_headers(output: &str) -> (Vec<(String, String)>, &str) {
+        let mut headers = Vec::new();
+        let mut rest = output;
+
+        while rest.starts_with("HTTP/") {
+            let Some((block, body)) = rest.split_once("\r\n\r\n").or_else(|| rest.split_once("\n\n")) else {
+                break;
+            };
+
+            headers = block.lines()
+                .skip(1)
+                .filter_map(|line| line.split_once(':'))
+                .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+                .collect();
+            rest = body;
+        }
+
+        (headers, rest)
+    }
+}
+
+impl HttpTransport for CurlTransport {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse> {
+        let output = Command::new("curl")
+            .args(Self::build_args(request))
+            .output()
+            .context("Failed to execute curl command")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow::anyhow!("curl command failed: {}", stderr));
+        }
+
+        let stdout = String::from_utf8(output.stdout)
+            .context("Failed to parse curl output as UTF-8")?;
+
+        Self::parse_output(&stdout)
+    }
+}
+
+/// In-memory transport that replays queued responses and records requests
+///
+/// Clones share the same queue and request log, so a test can keep a handle
+/// after moving the transport into `LeetCodeApi`.
+#[derive(Debug, Clone, Default)]
+pub struct MockTransport {
+    responses: Rc<RefCell<VecDeque<Result<HttpResponse, String>>>>,
+    requests: Rc<RefCell<Vec<HttpRequest>>>,
+}
+
+impl MockTransport {
+    /// Create an empty mock transport
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a mock transport that replays the given 200 OK bodies in order
+    pub fn with_responses<I, S>(bodies: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let transport = Self::new();
+        for body in bodies {
+            transport.push_response(body.as_ref());
+        }
+        transport
+    }
+
+    /// Queue a 200 OK response
+    pub fn push_response(&self, body: &str) {
+        self.push(HttpResponse::ok(body));
+    }
+
+    /// Queue a response with an explicit status code
+    pub fn push(&self, response: HttpResponse) {
+        self.responses.borrow_mut().push_back(Ok(response));
+    }
+
+    /// Queue a transport-level failure (e.g. curl not reachable)
+    pub fn push_error(&self, message: &str) {
+        self.responses.borrow_mut().push_back(Err(message.to_string()));
+    }
+
+    /// Requests sent through this transport so far
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.requests.borrow().clone()
+    }
+
+    /// Number of queued responses not yet consumed
+    pub fn remaining(&self) -> usize {
+        self.responses.borrow().len()
+    }
+}
+
+impl HttpTransport for MockTransport {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse> {
+        self.requests.borrow_mut().push(request.clone());
+
+        match self.responses.borrow_mut().pop_front() {
+            Some(Ok(response)) => Ok(response),
+            Some(Err(message)) => Err(anyhow::anyhow!(message)),
+            None => Err(anyhow::anyhow!("No mock response queued for {}", request.url)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_curl_args_post() {
+        let request = HttpRequest::post("https://leetcode.com/graphql/", "{}".to_string())
+            .header("Content-Type", "application/json");
+        let args = CurlTransport::build_args(&request);
+
+        assert_eq!(args[0..4], ["-s", "-X", "POST", "https://leetcode.com/graphql/"]);
+        assert!(args.contains(&"Content-Type: application/json".to_string()));
+        assert_eq!(args[args.len() - 2..], ["-d", "{}"]);
+    }
+
+    #[test]
+    fn test_curl_args_get_has_no_body() {
+        let request = HttpRequest::get("https://leetcode.com/");
+        let args = CurlTransport::build_args(&request);
+
+        assert!(args.contains(&"GET".to_string()));
+        assert!(!args.contains(&"-d".to_string()));
+    }
+
+    #[test]
+    fn test_curl_parse_output() {
+        let output = format!("{{\"data\": null}}{}429", STATUS_MARKER);
+        let response = CurlTransport::parse_output(&output).unwrap();
+        assert_eq!(response.status, 429);
+        assert_eq!(response.body, "{\"data\": null}");
+        assert!(!response.is_success());
+
+        assert!(CurlTransport::parse_output("no status").is_err());
+    }
+
+    #[test]
+    fn test_curl_parse_output_with_headers() {
+        let output = format!(
+            "HTTP/1.1 100 Continue\r\n\r\nHTTP/2 429\r\ncontent-type: text/html\r\nretry-after: 7\r\n\r\n<html>slow down</html>{}429",
+            STATUS_MARKER
+        );
+        let response = CurlTransport::parse_output(&output).unwrap();
+        assert_eq!(response.status, 429);
+        assert_eq!(response.body, "<html>slow down</html>");
+        assert_eq!(response.header_value("Content-Type"), Some("text/html"));
+        assert_eq!(response.retry_after(), Some(Duration::from_secs(7)));
+    }
+
+    #[test]
+    fn test_retry_after_http_date() {
+        let past = HttpResponse::new(503, "").header("Retry-After", "Wed, 21 Oct 2015 07:28:00 GMT");
+        assert_eq!(past.retry_after(), Some(Duration::ZERO));
+        assert_eq!(HttpResponse::new(503, "").header("Retry-After", "soon").retry_after(), None);
+        assert_eq!(HttpResponse::ok("").retry_after(), None);
+    }
+
+    #[test]
+    fn test_mock_transport_replays_in_order() {
+        let transport = MockTransport::with_responses(["first", "second"]);
+        let request = HttpRequest::get("https://example.com/");
+
+        assert_eq!(transport.send(&request).unwrap().body, "first");
+        assert_eq!(transport.send(&request).unwrap().body, "second");
+        assert!(transport.send(&request).is_err());
+        assert_eq!(transport.requests().len(), 3);
+    }
+
+    #[test]
+    fn test_mock_transport_shared_between_clones() {
+        let transport = MockTransport::new();
+        let handle = transport.clone();
+        transport.push_error("connection refused");
+
+        let result = handle.send(&HttpRequest::get("https://example.com/"));
+        assert!(result.unwrap_err().to_string().contains("connection refused"));
+        assert_eq!(transport.requests().len(), 1);
+        assert_eq!(transport.remaining(), 0);
+    }
+
+    #[test]
+    fn test_header_value_case_insensitive() {
+        let request = HttpRequest::get("https://example.com/").header("Cookie", "a=b");
+        assert_eq!(request.header_value("cookie"), Some("a=b"));
+        assert_eq!(request.header_value("x-csrftoken"), None);
+    }
+}
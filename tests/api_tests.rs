@@ -0,0 +1,424 @@
+use std::time::Duration;
+use zed_leetcode::api::{LeetCodeApi, PollOptions, RetryPolicy};
+use zed_leetcode::error::LeetCodeError;
+use zed_leetcode::models::{Difficulty, ProblemDetail, ProblemFilters, Site, SubmissionStatus, TestStatus};
+use zed_leetcode::transport::{HttpMethod, HttpResponse, MockTransport};
+
+const PROBLEM_LIST_FIXTURE: &str = r#"{
+    "data": {
+        "problemsetQuestionList": {
+            "total": 2,
+            "questions": [
+                {
+                    "questionId": "1",
+                    "questionFrontendId": "1",
+                    "title": "Two Sum",
+                    "titleSlug": "two-sum",
+                    "difficulty": "EASY",
+                    "topicTags": [{"name": "Array", "slug": "array"}],
+                    "isPaidOnly": false,
+                    "acRate": 0.49
+                },
+                {
+                    "questionId": "2",
+                    "questionFrontendId": "2",
+                    "title": "Add Two Numbers",
+                    "titleSlug": "add-two-numbers",
+                    "difficulty": "MEDIUM",
+                    "topicTags": [],
+                    "isPaidOnly": false,
+                    "acRate": 0.37
+                }
+            ]
+        }
+    }
+}"#;
+
+/// Poll without sleeping so pending responses don't slow the tests down
+fn immediate_polling() -> PollOptions {
+    PollOptions {
+        initial_interval: Duration::ZERO,
+        max_interval: Duration::ZERO,
+        ..Default::default()
+    }
+}
+
+/// Retry without sleeping
+fn immediate_retries() -> RetryPolicy {
+    RetryPolicy {
+        initial_backoff: Duration::ZERO,
+        ..Default::default()
+    }
+}
+
+fn authenticated_api(transport: &MockTransport) -> LeetCodeApi {
+    LeetCodeApi::with_session("LEETCODE_SESSION=test".to_string())
+        .with_csrf_token("csrf-test".to_string())
+        .with_transport(transport.clone())
+        .with_poll_options(immediate_polling())
+        .with_retry_policy(immediate_retries())
+}
+
+#[test]
+fn test_fetch_problems_with_mock_transport() {
+    let transport = MockTransport::with_responses([PROBLEM_LIST_FIXTURE]);
+    let api = authenticated_api(&transport);
+
+    let page = api.fetch_problems(&ProblemFilters::default()).unwrap();
+    assert_eq!(page.questions.len(), 2);
+    assert_eq!(page.questions[0].title_slug, "two-sum");
+    assert_eq!(page.questions[1].difficulty, Difficulty::Medium);
+    assert_eq!((page.total, page.skip, page.limit), (2, 0, 50));
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].header_value("Cookie"), Some("LEETCODE_SESSION=test"));
+    assert!(requests[0].body.as_ref().unwrap().contains("problemsetQuestionList"));
+}
+
+#[test]
+fn test_fetch_problems_graphql_error() {
+    let transport = MockTransport::with_responses([r#"{"errors": [{"message": "rate limited"}]}"#]);
+    let api = authenticated_api(&transport);
+
+    let result = api.fetch_problems(&ProblemFilters::default());
+    assert_eq!(result.unwrap_err(), LeetCodeError::RateLimited { retry_after: None });
+}
+
+#[test]
+fn test_fetch_problems_http_error() {
+    let transport = MockTransport::new();
+    transport.push(HttpResponse::new(502, "Bad Gateway"));
+    let api = authenticated_api(&transport).with_retry_policy(RetryPolicy::none());
+
+    let result = api.fetch_problems(&ProblemFilters::default());
+    assert!(matches!(result.unwrap_err(), LeetCodeError::Network(message) if message.contains("HTTP 502")));
+}
+
+#[test]
+fn test_run_test_polls_until_complete() {
+    let transport = MockTransport::with_responses([
+        r#"{"data": {"interpretSolution": {"submissionId": "runcode_1", "state": "PENDING", "statusCode": 0, "status": "Pending"}}}"#,
+        r#"{"data": {"interpretSolution": {"submissionId": "runcode_1", "state": "STARTED", "statusCode": 0, "status": "Judging"}}}"#,
+        r#"{"data": {"interpretSolution": {
+            "submissionId": "runcode_1", "state": "SUCCESS", "statusCode": 11, "status": "Wrong Answer",
+            "totalTestcases": 3, "correctTestcases": 1,
+            "lastTestcase": "[2,7,11,15]\n9", "expectedOutput": "[0,1]", "codeOutput": "[1,0]"
+        }}}"#,
+    ]);
+    let api = authenticated_api(&transport);
+
+    let result = api.run_test("two-sum", "class Solution: pass", "python3", "[3,3]\n6").unwrap();
+    assert_eq!(result.status, TestStatus::WrongAnswer);
+    assert_eq!(result.passed_tests, 1);
+    assert_eq!(result.total_tests, 3);
+    assert!(result.failed_test_case.unwrap().contains("Expected: [0,1]"));
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[0].header_value("x-csrftoken"), Some("csrf-test"));
+    assert_eq!(requests[0].header_value("Cookie"), Some("LEETCODE_SESSION=test; csrftoken=csrf-test"));
+    let body: serde_json::Value = serde_json::from_str(requests[0].body.as_ref().unwrap()).unwrap();
+    assert_eq!(body["variables"]["dataInput"], "[3,3]\n6");
+    assert_eq!(transport.remaining(), 0);
+}
+
+#[test]
+fn test_mutation_without_csrf_token_is_rejected_locally() {
+    let transport = MockTransport::new();
+    let api = LeetCodeApi::with_session("LEETCODE_SESSION=test".to_string())
+        .with_transport(transport.clone());
+
+    let result = api.run_test("two-sum", "class Solution: pass", "python3", "[3,3]\n6");
+    assert_eq!(result.unwrap_err(), LeetCodeError::CsrfMismatch);
+    assert!(transport.requests().is_empty());
+}
+
+#[test]
+fn test_submit_solution_accepted() {
+    let transport = MockTransport::with_responses([
+        r#"{"data": {"submitSolution": {"submissionId": "42"}}}"#,
+        r#"{"data": {"submissionDetails": {
+            "submissionId": "42", "state": "SUCCESS", "statusCode": 10, "status": "Accepted",
+            "runtime": "3", "memory": "2.1", "runtimePercentile": 97.5, "memoryPercentile": 60.0,
+            "totalCorrect": 63, "totalTestcases": 63
+        }}}"#,
+    ]);
+    let api = authenticated_api(&transport);
+
+    let result = api.submit_solution("two-sum", "impl Solution {}", "rust").unwrap();
+    assert_eq!(result.status, SubmissionStatus::Accepted);
+    assert_eq!(result.runtime, Some(3));
+    assert_eq!(result.total_correct, Some(63));
+    assert!(transport.requests()[1].body.as_ref().unwrap().contains("\"42\""));
+}
+
+#[test]
+fn test_transport_failure_is_reported() {
+    let transport = MockTransport::new();
+    transport.push_error("Could not resolve host");
+    let api = authenticated_api(&transport);
+
+    let result = api.submit_solution("two-sum", "impl Solution {}", "rust");
+    assert!(matches!(result.unwrap_err(), LeetCodeError::Network(message) if message.contains("Could not resolve host")));
+}
+
+fn two_sum_detail() -> ProblemDetail {
+    ProblemDetail {
+        id: "1".to_string(),
+        frontend_id: "1".to_string(),
+        title: "Two Sum".to_string(),
+        title_slug: "two-sum".to_string(),
+        content: String::new(),
+        difficulty: Difficulty::Easy,
+        tags: vec![],
+        code_snippets: vec![],
+        sample_test_case: "[2,7,11,15]\n9".to_string(),
+        example_testcases: None,
+        translated_title: None,
+        translated_content: None,
+    }
+}
+
+#[test]
+fn test_run_test_rest_endpoints() {
+    let transport = MockTransport::with_responses([
+        r#"{"interpret_id": "runcode_1700000000.1_abc", "test_case": "[2,7,11,15]\n9"}"#,
+        r#"{"state": "PENDING"}"#,
+        r#"{"state": "SUCCESS", "status_code": 10, "status_msg": "Accepted", "run_success": true,
+            "correct_answer": true, "status_runtime": "1 ms", "status_memory": "2.2 MB",
+            "code_answer": ["[0,1]"], "expected_code_answer": ["[0,1]"], "std_output": "",
+            "total_correct": 1, "total_testcases": 1}"#,
+    ]);
+    let api = authenticated_api(&transport);
+
+    let code = "class Solution:\n\tdef twoSum(self): return \"\\\\\"";
+    let result = api.run_test_rest(&two_sum_detail(), code, "python3", "[2,7,11,15]\n9").unwrap();
+    assert_eq!(result.status, TestStatus::Success);
+    assert_eq!(result.runtime, Some(1));
+    assert_eq!(result.memory, Some(2.2));
+    assert_eq!(result.passed_tests, 1);
+    assert_eq!(result.cases.len(), 1);
+    assert!(result.cases[0].passed);
+
+    let requests = transport.requests();
+    assert_eq!(requests[0].method, HttpMethod::Post);
+    assert_eq!(requests[0].url, "https://leetcode.com/problems/two-sum/interpret_solution/");
+    assert_eq!(requests[0].header_value("Referer"), Some("https://leetcode.com/problems/two-sum/"));
+
+    let body: serde_json::Value = serde_json::from_str(requests[0].body.as_ref().unwrap()).unwrap();
+    assert_eq!(body["question_id"], "1");
+    assert_eq!(body["typed_code"], code);
+    assert_eq!(body["data_input"], "[2,7,11,15]\n9");
+
+    assert_eq!(requests[1].method, HttpMethod::Get);
+    assert_eq!(requests[1].url, "https://leetcode.com/submissions/detail/runcode_1700000000.1_abc/check/");
+    assert_eq!(requests.len(), 3);
+}
+
+#[test]
+fn test_submit_solution_rest_wrong_answer() {
+    let transport = MockTransport::with_responses([
+        r#"{"submission_id": 1234567}"#,
+        r#"{"state": "SUCCESS", "status_code": 11, "status_msg": "Wrong Answer",
+            "total_correct": 10, "total_testcases": 63, "last_testcase": "[3,3]\n6",
+            "expected_output": "[0,1]", "code_output": "[]", "std_output": ""}"#,
+    ]);
+    let api = authenticated_api(&transport);
+
+    let result = api.submit_solution_rest(&two_sum_detail(), "impl Solution {}", "rust").unwrap();
+    assert_eq!(result.status, SubmissionStatus::WrongAnswer);
+    assert_eq!(result.total_correct, Some(10));
+    assert_eq!(result.total_testcases, Some(63));
+    assert!(result.failed_test_case.unwrap().contains("Input: [3,3]\n6"));
+
+    let requests = transport.requests();
+    assert_eq!(requests[0].url, "https://leetcode.com/problems/two-sum/submit/");
+    assert_eq!(requests[1].url, "https://leetcode.com/submissions/detail/1234567/check/");
+}
+
+#[test]
+fn test_polling_timeout_reports_submission_id() {
+    let transport = MockTransport::with_responses([
+        r#"{"interpret_id": "runcode_42"}"#,
+        r#"{"state": "STARTED"}"#,
+    ]);
+    let api = authenticated_api(&transport)
+        .with_poll_options(PollOptions::with_timeout(Duration::ZERO));
+
+    let error = api.run_test_rest(&two_sum_detail(), "impl Solution {}", "rust", "[3,3]\n6")
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("did not finish within 0s"));
+    assert!(error.contains("runcode_42"));
+    assert_eq!(transport.remaining(), 0);
+}
+
+#[test]
+fn test_http_status_maps_to_error_variant() {
+    let transport = MockTransport::new();
+    transport.push(HttpResponse::new(429, "<html>Too Many Requests</html>"));
+    transport.push(HttpResponse::new(403, "Forbidden"));
+    let api = authenticated_api(&transport).with_retry_policy(RetryPolicy::none());
+
+    assert_eq!(
+        api.fetch_problems(&ProblemFilters::default()).unwrap_err(),
+        LeetCodeError::RateLimited { retry_after: None }
+    );
+    assert_eq!(
+        api.submit_solution_rest(&two_sum_detail(), "impl Solution {}", "rust").unwrap_err(),
+        LeetCodeError::CsrfMismatch
+    );
+}
+
+#[test]
+fn test_premium_question_detail() {
+    let transport = MockTransport::with_responses([
+        r#"{"data": {"question": {"questionId": "156", "questionFrontendId": "156", "title": "Binary Tree Upside Down",
+            "titleSlug": "binary-tree-upside-down", "content": null, "difficulty": "MEDIUM", "topicTags": [],
+            "codeSnippets": null, "sampleTestCase": null, "exampleTestcases": null}}}"#,
+    ]);
+    let api = authenticated_api(&transport);
+
+    let result = api.fetch_problem_detail("binary-tree-upside-down");
+    assert_eq!(result.unwrap_err(), LeetCodeError::PremiumRequired);
+}
+
+#[test]
+fn test_queries_are_retried_after_server_errors() {
+    let transport = MockTransport::new();
+    transport.push(HttpResponse::new(503, "<html>Service Unavailable</html>"));
+    transport.push(HttpResponse::new(429, "<html>Too Many Requests</html>").header("Retry-After", "0"));
+    transport.push_response(PROBLEM_LIST_FIXTURE);
+    let api = authenticated_api(&transport);
+
+    let page = api.fetch_problems(&ProblemFilters::default()).unwrap();
+    assert_eq!(page.questions.len(), 2);
+    assert_eq!(transport.requests().len(), 3);
+}
+
+#[test]
+fn test_retries_give_up_after_max_retries() {
+    let transport = MockTransport::new();
+    for _ in 0..4 {
+        transport.push(HttpResponse::new(502, "Bad Gateway"));
+    }
+    let api = authenticated_api(&transport);
+
+    let result = api.fetch_problems(&ProblemFilters::default());
+    assert!(matches!(result.unwrap_err(), LeetCodeError::Network(message) if message.contains("HTTP 502")));
+    assert_eq!(transport.requests().len(), 4);
+}
+
+#[test]
+fn test_client_errors_are_not_retried() {
+    let transport = MockTransport::new();
+    transport.push(HttpResponse::new(400, r#"{"errors": [{"message": "Variable \"$skip\" got invalid value"}]}"#));
+    transport.push(HttpResponse::new(422, "Unprocessable Entity"));
+    let api = authenticated_api(&transport);
+
+    let result = api.fetch_problems(&ProblemFilters::default());
+    assert!(matches!(result.unwrap_err(), LeetCodeError::Api(message) if message.contains("got invalid value")));
+    assert_eq!(transport.requests().len(), 1);
+
+    let result = api.fetch_problems(&ProblemFilters::default());
+    assert_eq!(result.unwrap_err(), LeetCodeError::Api("LeetCode returned HTTP 422".to_string()));
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[test]
+fn test_long_retry_after_is_reported_not_waited() {
+    let transport = MockTransport::new();
+    transport.push(HttpResponse::new(429, "").header("Retry-After", "120"));
+    let api = authenticated_api(&transport);
+
+    let result = api.fetch_problems(&ProblemFilters::default());
+    assert_eq!(result.unwrap_err(), LeetCodeError::RateLimited { retry_after: Some(Duration::from_secs(120)) });
+    assert_eq!(transport.requests().len(), 1);
+}
+
+#[test]
+fn test_mutations_are_not_retried() {
+    let transport = MockTransport::new();
+    transport.push(HttpResponse::new(502, "Bad Gateway"));
+    let api = authenticated_api(&transport);
+
+    let result = api.submit_solution_rest(&two_sum_detail(), "impl Solution {}", "rust");
+    assert!(matches!(result.unwrap_err(), LeetCodeError::Network(_)));
+    assert_eq!(transport.requests().len(), 1);
+}
+
+#[test]
+fn test_fetch_daily_challenge() {
+    let transport = MockTransport::with_responses([
+        r#"{"data": {"activeDailyCodingChallengeQuestion": {"date": "2024-01-15", "userStatus": "NotStart",
+            "link": "/problems/two-sum/", "question": {"titleSlug": "two-sum"}}}}"#,
+    ]);
+    let api = authenticated_api(&transport);
+
+    let challenge = api.fetch_daily_challenge().unwrap();
+    assert_eq!(challenge.date, "2024-01-15");
+    assert_eq!(challenge.question.title_slug, "two-sum");
+    assert!(!challenge.is_completed());
+    assert!(transport.requests()[0].body.as_ref().unwrap().contains("activeDailyCodingChallengeQuestion"));
+}
+
+#[test]
+fn test_fetch_daily_challenge_china() {
+    let transport = MockTransport::with_responses([
+        r#"{"data": {"todayRecord": [{"date": "2024-01-15", "userStatus": "FINISH", "question": {"titleSlug": "two-sum"}}]}}"#,
+        r#"{"data": {"todayRecord": []}}"#,
+    ]);
+    let api = authenticated_api(&transport).with_site(Site::China);
+
+    let challenge = api.fetch_daily_challenge().unwrap();
+    assert!(challenge.is_completed());
+    assert!(transport.requests()[0].url.starts_with("https://leetcode.cn"));
+
+    assert!(matches!(api.fetch_daily_challenge().unwrap_err(), LeetCodeError::NotFound(_)));
+}
+
+#[test]
+fn test_fetch_random_question_sends_filters() {
+    let transport = MockTransport::with_responses([
+        r#"{"data": {"randomQuestion": {"titleSlug": "two-sum"}}}"#,
+        r#"{"data": {"randomQuestion": null}}"#,
+    ]);
+    let api = authenticated_api(&transport);
+    let filters = ProblemFilters {
+        difficulty: Some(Difficulty::Easy),
+        status: Some("NOT_STARTED".to_string()),
+        premium_only: Some(false),
+        ..Default::default()
+    };
+
+    assert_eq!(api.fetch_random_question(&filters).unwrap().as_deref(), Some("two-sum"));
+    assert_eq!(api.fetch_random_question(&filters).unwrap(), None);
+
+    let body: serde_json::Value = serde_json::from_str(transport.requests()[0].body.as_ref().unwrap()).unwrap();
+    assert_eq!(body["operationName"], "randomQuestion");
+    assert_eq!(body["variables"]["filters"]["difficulty"], "EASY");
+    assert_eq!(body["variables"]["filters"]["status"], "NOT_STARTED");
+    assert_eq!(body["variables"]["filters"]["premiumOnly"], false);
+}
+
+#[test]
+fn test_fetch_problem_catalog_pages_until_total() {
+    let first_page = PROBLEM_LIST_FIXTURE.replace(r#""total": 2"#, r#""total": 3"#);
+    let second_page = r#"{"data": {"problemsetQuestionList": {"total": 3, "questions": [
+        {"questionId": "3", "questionFrontendId": "3", "title": "Longest Substring Without Repeating Characters",
+         "titleSlug": "longest-substring-without-repeating-characters", "difficulty": "MEDIUM", "topicTags": [],
+         "isPaidOnly": false, "acRate": 0.34, "status": "ac"}
+    ]}}}"#;
+    let transport = MockTransport::with_responses([first_page.as_str(), second_page]);
+    let api = authenticated_api(&transport);
+
+    let problems = api.fetch_problem_catalog().unwrap();
+    assert_eq!(problems.len(), 3);
+    assert_eq!(problems[2].status.as_deref(), Some("ac"));
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    let body: serde_json::Value = serde_json::from_str(requests[1].body.as_ref().unwrap()).unwrap();
+    assert_eq!(body["variables"]["skip"], 2);
+}
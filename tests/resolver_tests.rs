@@ -0,0 +1,85 @@
+use tempfile::TempDir;
+use zed_leetcode::api::LeetCodeApi;
+use zed_leetcode::file_manager::FileManager;
+use zed_leetcode::models::{ProblemIndex, ProblemIndexEntry};
+use zed_leetcode::resolver::resolve_title_slug;
+use zed_leetcode::transport::MockTransport;
+
+const CATALOG_PAGE: &str = r#"{
+    "data": {
+        "problemsetQuestionList": {
+            "total": 2,
+            "questions": [
+                {
+                    "questionId": "1", "questionFrontendId": "1", "title": "Two Sum",
+                    "titleSlug": "two-sum", "difficulty": "EASY", "topicTags": [],
+                    "isPaidOnly": false, "acRate": 0.49
+                },
+                {
+                    "questionId": "50", "questionFrontendId": "50", "title": "Pow(x, n)",
+                    "titleSlug": "powx-n", "difficulty": "MEDIUM", "topicTags": [],
+                    "isPaidOnly": false, "acRate": 0.35
+                }
+            ]
+        }
+    }
+}"#;
+
+fn setup(transport: &MockTransport) -> (LeetCodeApi, FileManager, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let file_manager = FileManager::new_with_path(temp_dir.path().to_path_buf()).unwrap();
+    let api = LeetCodeApi::with_session("LEETCODE_SESSION=test".to_string())
+        .with_transport(transport.clone());
+    (api, file_manager, temp_dir)
+}
+
+#[test]
+fn test_resolve_id_builds_and_caches_index() {
+    let transport = MockTransport::with_responses([CATALOG_PAGE]);
+    let (api, file_manager, _temp_dir) = setup(&transport);
+
+    assert_eq!(resolve_title_slug("1", &api, &file_manager).unwrap(), "two-sum");
+    assert!(file_manager.load_problem_index().unwrap().is_some());
+
+    // Second lookup is served from the cached index without a network call
+    assert_eq!(resolve_title_slug("Pow(x, n)", &api, &file_manager).unwrap(), "powx-n");
+    assert_eq!(transport.requests().len(), 1);
+}
+
+#[test]
+fn test_resolve_slug_and_url_without_network() {
+    let transport = MockTransport::new();
+    let (api, file_manager, _temp_dir) = setup(&transport);
+
+    assert_eq!(resolve_title_slug("two-sum", &api, &file_manager).unwrap(), "two-sum");
+    assert_eq!(
+        resolve_title_slug("https://leetcode.com/problems/powx-n/description/", &api, &file_manager).unwrap(),
+        "powx-n"
+    );
+    assert!(transport.requests().is_empty());
+}
+
+#[test]
+fn test_resolve_refreshes_stale_index() {
+    let transport = MockTransport::with_responses([CATALOG_PAGE]);
+    let (api, file_manager, _temp_dir) = setup(&transport);
+
+    let stale = ProblemIndex::new(vec![ProblemIndexEntry {
+        frontend_id: "1".to_string(),
+        title: "Two Sum".to_string(),
+        title_slug: "two-sum".to_string(),
+    }]);
+    file_manager.save_problem_index(&stale).unwrap();
+
+    assert_eq!(resolve_title_slug("50", &api, &file_manager).unwrap(), "powx-n");
+    assert_eq!(transport.requests().len(), 1);
+}
+
+#[test]
+fn test_resolve_unknown_problem() {
+    let transport = MockTransport::with_responses([CATALOG_PAGE]);
+    let (api, file_manager, _temp_dir) = setup(&transport);
+
+    let result = resolve_title_slug("99999", &api, &file_manager);
+    assert!(result.unwrap_err().to_string().contains("Problem not found: 99999"));
+}